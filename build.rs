@@ -0,0 +1,49 @@
+/// Generates the solution registry of the main binary from the files in `src/bin` if the `registry` feature is enabled.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    // without the feature, the main binary does not compile the solutions and runs them as their own binaries.
+    if env::var_os("CARGO_FEATURE_REGISTRY").is_none() {
+        return;
+    }
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut bins: Vec<_> = fs::read_dir(bin_dir)
+        .unwrap()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect();
+    bins.sort();
+
+    let mut entries = String::new();
+
+    for path in bins {
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+
+        // only register binaries that can be compiled as a module and expose a solution.
+        // binaries of other years are named like `2023-05`, which becomes the module `bin_2023_05`.
+        let is_solution = fs::read_to_string(&path).is_ok_and(|s| s.contains("solution!("));
+        let is_ident = name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+        if is_solution && is_ident {
+            let module = name.replace('-', "_");
+            entries.push_str(&format!("    {name:?} => bin_{module} at {path:?},\n"));
+        }
+    }
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(
+        out_path,
+        format!("advent_of_code::register_solutions! {{\n{entries}}}\n"),
+    )
+    .unwrap();
+}
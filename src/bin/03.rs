@@ -1,6 +1,6 @@
 use regex::Regex;
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(3, variants = { parse: 1 => part_one_parse, parse: 2 => part_two_parse });
 
 pub fn part_one(input: &str) -> Option<u32> {
     let regex = Regex::new(r"mul\(\d{1,3},\d{1,3}\)").unwrap();
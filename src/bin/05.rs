@@ -1,12 +1,11 @@
-#![feature(is_sorted)]
-
+use advent_of_code::template::split_sections;
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, variants = { another: 1 => part_one_another });
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (rules, pages) = input.split_once("\r\n\r\n").unwrap();
+    let (rules, pages) = split_sections(input).unwrap();
 
     let mut orderings: HashMap<u32, HashSet<u32>> = HashMap::new();
     for rule in rules.lines() {
@@ -37,7 +36,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_one_another(input: &str) -> Option<u32> {
-    let (rules, pages) = input.split_once("\r\n\r\n").unwrap();
+    let (rules, pages) = split_sections(input).unwrap();
     let mut orderings: HashMap<(u32, u32), Ordering> = HashMap::new();
     for rule in rules.lines() {
         let (n1, n2) = rule
@@ -61,7 +60,7 @@ pub fn part_one_another(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let (rules, pages) = input.split_once("\r\n\r\n").unwrap();
+    let (rules, pages) = split_sections(input).unwrap();
     let mut orderings: HashMap<(u32, u32), Ordering> = HashMap::new();
     for rule in rules.lines() {
         let (n1, n2) = rule
@@ -97,7 +96,6 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        advent_of_code::template::assert_examples(DAY, 2, part_two);
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, parse = parse);
 
 fn parse(input: &str) -> HashMap<u64, u64> {
     let mut stones: HashMap<u64, u64> = HashMap::new();
@@ -30,8 +30,8 @@ fn blink(stones: &HashMap<u64, u64>) -> HashMap<u64, u64> {
     new
 }
 
-fn both(input: &str) -> (u64, u64) {
-    let mut stones = parse(input);
+fn both(stones: &HashMap<u64, u64>) -> (u64, u64) {
+    let mut stones = stones.clone();
     let mut p1 = 0;
     for i in (0..75) {
         if i == 25 {
@@ -43,12 +43,12 @@ fn both(input: &str) -> (u64, u64) {
     (p1, stones.values().sum())
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    Some(both(input).0)
+pub fn part_one(stones: &HashMap<u64, u64>) -> Option<u64> {
+    Some(both(stones).0)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    Some(both(input).1)
+pub fn part_two(stones: &HashMap<u64, u64>) -> Option<u64> {
+    Some(both(stones).1)
 }
 
 #[cfg(test)]
@@ -57,13 +57,6 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        advent_of_code::template::assert_examples(DAY, 1, |input| part_one(&parse(input)));
     }
 }
@@ -1,3 +1,4 @@
+use advent_of_code::template::sections;
 use itertools::Itertools;
 
 advent_of_code::solution!(13);
@@ -16,7 +17,7 @@ impl Point {
 
 fn parse(input: &str) -> Vec<[Point; 3]> {
     let mut machines = Vec::new();
-    for block in input.split("\r\n\r\n") {
+    for block in sections(input) {
         let (adx, ady, bdx, bdy, x, y) = block
             .split(|c: char| !c.is_ascii_digit())
             .filter(|c| !c.is_empty())
@@ -70,10 +71,4 @@ mod tests {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(480));
     }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
 }
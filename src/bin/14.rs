@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-advent_of_code::solution!(14);
+advent_of_code::solution!(14, params = { rows: i64 = 103, cols: i64 = 101 });
 
 #[derive(PartialEq, Eq, Hash, Clone, Copy)]
 struct Point {
@@ -29,8 +29,6 @@ impl Robot {
     }
 }
 
-const ROWS: i64 = 103;
-const COLS: i64 = 101;
 fn parse(input: &str) -> Vec<Robot> {
     let mut robots = Vec::new();
     for line in input.lines() {
@@ -49,10 +47,10 @@ fn parse(input: &str) -> Vec<Robot> {
     robots
 }
 
-fn safety(robots: &[Robot]) -> usize {
+fn safety(robots: &[Robot], params: &Params) -> usize {
     let mut sectors = [0; 4];
-    let col_mid = COLS / 2;
-    let row_mid = ROWS / 2;
+    let col_mid = params.cols / 2;
+    let row_mid = params.rows / 2;
     for &Robot { pos, .. } in robots {
         if pos.row == row_mid || pos.col == col_mid {
             continue;
@@ -70,21 +68,21 @@ fn safety(robots: &[Robot]) -> usize {
 }
 
 
-pub fn part_one(input: &str) -> Option<i64> {
+pub fn part_one(input: &str, params: &Params) -> Option<i64> {
     let mut robots = parse(input);
     for Robot { pos, vel } in &mut robots {
-        pos.row = (pos.row + vel.row * 100).rem_euclid(ROWS);
-        pos.col = (pos.col + vel.col * 100).rem_euclid(COLS);
+        pos.row = (pos.row + vel.row * 100).rem_euclid(params.rows);
+        pos.col = (pos.col + vel.col * 100).rem_euclid(params.cols);
     }
-    Some(safety(&robots) as i64)
+    Some(safety(&robots, params) as i64)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str, params: &Params) -> Option<u32> {
     let mut robots = parse(input);
     for i in 0.. {
         for Robot { pos, vel } in &mut robots {
-            pos.row = (pos.row + vel.row).rem_euclid(ROWS);
-            pos.col = (pos.col + vel.col).rem_euclid(COLS);
+            pos.row = (pos.row + vel.row).rem_euclid(params.rows);
+            pos.col = (pos.col + vel.col).rem_euclid(params.cols);
         }
         if robots.iter().map(|robot| robot.pos).all_unique() {
             return Some(i + 1);
@@ -99,13 +97,6 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        advent_of_code::template::assert_examples_with_params(DAY, 1, part_one);
     }
 }
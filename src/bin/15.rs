@@ -1,3 +1,4 @@
+use advent_of_code::template::split_sections;
 use itertools::Itertools;
 use std::collections::vec_deque::VecDeque;
 use std::collections::{HashMap, HashSet};
@@ -166,7 +167,7 @@ fn gps(grid: &HashMap<Point, Tile>, item: Tile) -> i32 {
 }
 
 pub fn part_one(input: &str) -> Option<i32> {
-    let (grid_str, instructions_str) = input.split_once("\r\n\r\n").unwrap();
+    let (grid_str, instructions_str) = split_sections(input).unwrap();
 
     let mut grid = parse_grid(grid_str);
     let instructions = parse_instructions(instructions_str);
@@ -177,7 +178,7 @@ pub fn part_one(input: &str) -> Option<i32> {
 }
 
 pub fn part_two(input: &str) -> Option<i32> {
-    let (grid_str, instructions_str) = input.split_once("\r\n\r\n").unwrap();
+    let (grid_str, instructions_str) = split_sections(input).unwrap();
     let grid_str = embigger(grid_str);
     let mut grid = parse_grid(&grid_str);
     let instructions = parse_instructions(instructions_str);
@@ -193,13 +194,11 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        advent_of_code::template::assert_examples(DAY, 1, part_one);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        advent_of_code::template::assert_examples(DAY, 2, part_two);
     }
 }
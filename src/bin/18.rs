@@ -1,11 +1,7 @@
 use itertools::Itertools;
 use std::collections::{HashSet, VecDeque};
 
-advent_of_code::solution!(18);
-
-const ROWS: i32 = 71;
-const COLS: i32 = 71;
-const SIZE: usize = 1024;
+advent_of_code::solution!(18, params = { rows: i32 = 71, cols: i32 = 71, size: usize = 1024 });
 
 #[derive(Hash, PartialEq, Eq, Debug, Copy, Clone)]
 struct Point {
@@ -59,7 +55,12 @@ fn parse(input: &str) -> Vec<Point> {
         .collect_vec()
 }
 
-fn search(corrupted: HashSet<&Point>, start: Point, end: Point) -> Option<u32> {
+fn search(
+    corrupted: HashSet<&Point>,
+    start: Point,
+    end: Point,
+    params: &Params,
+) -> Option<u32> {
     let mut q = VecDeque::new();
     let mut seen = HashSet::new();
     q.push_back((start, 0));
@@ -68,7 +69,7 @@ fn search(corrupted: HashSet<&Point>, start: Point, end: Point) -> Option<u32> {
         if pos == end {
             return Some(cost);
         }
-        for neighbour in pos.neighbour(ROWS, COLS) {
+        for neighbour in pos.neighbour(params.rows, params.cols) {
             if corrupted.contains(&neighbour) {
                 continue;
             }
@@ -80,18 +81,18 @@ fn search(corrupted: HashSet<&Point>, start: Point, end: Point) -> Option<u32> {
     None
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str, params: &Params) -> Option<u32> {
     let falling = parse(input);
-    let corrupted: HashSet<&Point> = falling.iter().take(SIZE).collect();
+    let corrupted: HashSet<&Point> = falling.iter().take(params.size).collect();
     let start = Point::new(0, 0);
-    let end = Point::new(ROWS - 1, COLS - 1);
-    search(corrupted, start, end)
+    let end = Point::new(params.rows - 1, params.cols - 1);
+    search(corrupted, start, end, params)
 }
 
-pub fn part_two(input: &str) -> Option<String> {
+pub fn part_two(input: &str, params: &Params) -> Option<String> {
     let falling = parse(input);
     let start = Point::new(0, 0);
-    let end = Point::new(ROWS - 1, COLS - 1);
+    let end = Point::new(params.rows - 1, params.cols - 1);
 
     let mut low = 0;
     let mut high = falling.len() - 1;
@@ -99,13 +100,14 @@ pub fn part_two(input: &str) -> Option<String> {
     while low < high {
         let mid = (low + high) / 2;
         let corrupted = falling.iter().take(mid).collect();
-        if search(corrupted, start, end).is_some() {
+        if search(corrupted, start, end, params).is_some() {
             low = mid + 1;
         } else {
             high = mid;
         }
     }
-    let last = falling[low];
+    // `low` is the smallest number of fallen bytes blocking the exit, the last of them is the answer.
+    let last = falling[low - 1];
     Some(format!("{},{}", last.col, last.row))
 }
 
@@ -115,13 +117,11 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        advent_of_code::template::assert_examples_with_params(DAY, 1, part_one);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        advent_of_code::template::assert_examples_with_params(DAY, 2, part_two);
     }
 }
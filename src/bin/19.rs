@@ -1,9 +1,10 @@
+use advent_of_code::template::split_sections;
 use std::collections::HashMap;
 
 advent_of_code::solution!(19);
 
 fn parse(input: &str) -> (Vec<&str>, Vec<&str>) {
-    let ( towels,  pattern) = input.split_once("\r\n\r\n").unwrap();
+    let ( towels,  pattern) = split_sections(input).unwrap();
     let towels = towels.split(", ").collect();
     let patterns = pattern.lines().collect();
     (towels, patterns)
@@ -53,13 +54,11 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        advent_of_code::template::assert_examples(DAY, 1, part_one);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(569808947758890));
+        advent_of_code::template::assert_examples(DAY, 2, part_two);
     }
 }
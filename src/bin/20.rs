@@ -2,7 +2,7 @@ use advent_of_code::Point;
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet, VecDeque};
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(20, params = { min_saving: u32 = 100 });
 
 #[derive(Eq, Ord, Clone, Copy, Hash, PartialEq, PartialOrd, Debug)]
 enum Tile {
@@ -74,7 +74,7 @@ fn build_distmap(grid: HashMap<Point, Tile>, start: Point, end: Point) -> HashMa
     panic!("No distmap found for {:?}", start);
 }
 
-fn count(distmap: &HashMap<Point, u32>, max_skip: u32) -> usize {
+fn count(distmap: &HashMap<Point, u32>, max_skip: u32, min_saving: u32) -> usize {
     distmap
         .iter()
         .tuple_combinations()
@@ -82,7 +82,7 @@ fn count(distmap: &HashMap<Point, u32>, max_skip: u32) -> usize {
             let skip_size = p1.manhattan_distance(p2);
             if skip_size <= max_skip {
                 let saved = c1.abs_diff(c2) - skip_size;
-                if saved >= 100  {
+                if saved >= min_saving {
                     return true;
                 }
             }
@@ -91,16 +91,16 @@ fn count(distmap: &HashMap<Point, u32>, max_skip: u32) -> usize {
         .count()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str, params: &Params) -> Option<u32> {
     let (start, end, grid) = parse(input);
     let dist_map = build_distmap(grid, start, end);
-    Some(count(&dist_map, 2) as u32)
+    Some(count(&dist_map, 2, params.min_saving) as u32)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str, params: &Params) -> Option<u32> {
     let (start, end, grid) = parse(input);
     let dist_map = build_distmap(grid, start, end);
-    Some(count(&dist_map, 20) as u32)
+    Some(count(&dist_map, 20, params.min_saving) as u32)
 }
 
 #[cfg(test)]
@@ -109,13 +109,11 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1411));
+        advent_of_code::template::assert_examples_with_params(DAY, 1, part_one);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1010263));
+        advent_of_code::template::assert_examples_with_params(DAY, 2, part_two);
     }
 }
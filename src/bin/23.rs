@@ -1,6 +1,6 @@
 use std::collections::{BTreeSet, HashMap, HashSet};
 
-advent_of_code::solution!(23);
+advent_of_code::solution!(23, variants = { other: 2 => part_two_other });
 
 fn parse(input: &str) -> HashMap<&str, HashSet<&str>> {
     let mut connections: HashMap<&str, HashSet<&str>> = HashMap::new();
@@ -77,6 +77,7 @@ pub fn part_two(input: &str) -> Option<String> {
         .into_iter()
         .max_by_key(|clique| clique.len())
         .unwrap()
+        .into_iter()
         .collect();
     clique.sort();
     Some(clique.join(","))
@@ -108,13 +109,11 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        advent_of_code::template::assert_examples(DAY, 1, part_one);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        advent_of_code::template::assert_examples(DAY, 2, part_two);
     }
 }
@@ -1,3 +1,4 @@
+use advent_of_code::template::split_sections;
 use itertools::Itertools;
 use std::cmp::PartialEq;
 use std::collections::HashMap;
@@ -29,7 +30,7 @@ struct Operation<'a> {
 }
 
 fn parse(input: &str) -> (HashMap<&str, bool>, HashMap<&str, Operation>) {
-    let (top, bottom) = input.split_once("\r\n\r\n").unwrap();
+    let (top, bottom) = split_sections(input).unwrap();
     let mut wires = HashMap::new();
     for line in top.lines() {
         let (left, right) = line.split_once(": ").unwrap();
@@ -225,13 +226,11 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        advent_of_code::template::assert_examples(DAY, 1, part_one);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        advent_of_code::template::assert_examples(DAY, 2, part_two);
     }
 }
@@ -1,3 +1,4 @@
+use advent_of_code::template::sections;
 use std::collections::{HashMap, HashSet};
 
 advent_of_code::solution!(25);
@@ -5,7 +6,7 @@ advent_of_code::solution!(25);
 fn parse(input: &str) -> (HashSet<[u8; 5]>, HashSet<[u8; 5]>) {
     let mut locks = HashSet::new();
     let mut keys = HashSet::new();
-    for block in input.split("\r\n\r\n") {
+    for block in sections(input) {
         let set = if block.starts_with('.') {
             &mut keys
         } else {
@@ -47,8 +48,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        advent_of_code::template::assert_examples(DAY, 1, part_one);
     }
 
     #[test]
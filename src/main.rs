@@ -1,79 +1,320 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, check_examples, download, inputs, read, scaffold, solve, time, verify, watch,
+};
+use advent_of_code::template::Registry;
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{templates::DEFAULT_TEMPLATE, Day, PuzzleId};
 #[cfg(feature = "today")]
 use std::process;
 
+// the solutions are not compiled into tests of the main binary, they are tested as their own binaries.
+#[cfg(all(feature = "registry", not(test)))]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+/// The solutions in `src/bin`, compiled into this binary with the `registry` feature.
+#[cfg(all(feature = "registry", not(test)))]
+fn registry() -> Registry {
+    solutions::registry()
+}
+
+/// The solutions in `src/bin`, which are built and run as their own binaries.
+#[cfg(not(all(feature = "registry", not(test))))]
+fn registry() -> Registry {
+    Registry::discover()
+}
+
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{
+        limits::{parse_memory_limit, parse_timeout, Limits},
+        parse_param_override,
+        runner::Variants,
+        templates::DEFAULT_TEMPLATE,
+        Day, OutputFormat, PuzzleId, Year,
+    };
     use std::process;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
+            force: bool,
+        },
+        DownloadAll {
+            year: Option<Year>,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
         },
+        EncryptInputs,
+        DecryptInputs,
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
             overwrite: bool,
+            template: String,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
+            bin: Option<String>,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            limits: Limits,
+            format: OutputFormat,
+            variants: Variants,
+            params: Vec<(String, String)>,
+        },
+        Watch {
+            puzzle: PuzzleId,
+            bin: Option<String>,
+            release: bool,
         },
         All {
+            year: Option<Year>,
             release: bool,
+            isolated: bool,
+            jobs: usize,
+            limits: Limits,
+            format: OutputFormat,
         },
         Time {
             all: bool,
+            year: Option<Year>,
             day: Option<Day>,
             store: bool,
+            isolated: bool,
+            limits: Limits,
+            format: OutputFormat,
+            compare_threshold: Option<f64>,
+        },
+        History {
+            puzzle: PuzzleId,
+        },
+        CheckExamples {
+            year: Option<Year>,
+            day: Option<Day>,
+            release: bool,
+            isolated: bool,
+            limits: Limits,
+        },
+        Verify {
+            year: Option<Year>,
+            day: Option<Day>,
+            release: bool,
+            isolated: bool,
+            limits: Limits,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// Parses the free arguments `[year] [day]`, e.g. `5`, `2023 5` or `2023`.
+    /// Flags and options need to be consumed before calling this.
+    fn parse_selection(
+        args: &mut pico_args::Arguments,
+    ) -> Result<(Option<Year>, Option<Day>), Box<dyn std::error::Error>> {
+        let Some(first) = args.opt_free_from_str::<String>()? else {
+            return Ok((None, None));
+        };
+
+        let (year, day) = match first.parse::<Year>() {
+            Ok(year) => (Some(year), args.opt_free_from_str()?),
+            Err(_) => (None, Some(first.parse()?)),
+        };
+
+        // the default year keeps the single-year layout.
+        let year = year.filter(|year| Some(*year) != Year::from_env());
+
+        Ok((year, day))
+    }
+
+    /// Parses the year of commands that run every day of it, which don't accept a day.
+    fn parse_year(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Year>, Box<dyn std::error::Error>> {
+        match parse_selection(args)? {
+            (year, None) => Ok(year),
+            (_, Some(day)) => {
+                Err(format!("unexpected day `{day}`, this command runs every day of a year").into())
+            }
+        }
+    }
+
+    /// Parses a puzzle addressed as `<day>` or `<year> <day>`.
+    fn parse_puzzle(
+        args: &mut pico_args::Arguments,
+    ) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        match parse_selection(args)? {
+            (year, Some(day)) => Ok(PuzzleId::new(year, day)),
+            (_, None) => Err(pico_args::Error::MissingArgument.into()),
+        }
+    }
+
+    /// Parses `--timeout` and `--memory-limit`, falling back to `AOC_TIMEOUT` and `AOC_MEMORY_LIMIT`.
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, Box<dyn std::error::Error>> {
+        let limits = Limits {
+            timeout: args.opt_value_from_fn("--timeout", parse_timeout)?,
+            memory: args.opt_value_from_fn("--memory-limit", parse_memory_limit)?,
+        };
+
+        Ok(limits.or(Limits::from_env()?))
+    }
+
+    fn parse_jobs(value: &str) -> Result<usize, String> {
+        match value.parse() {
+            Ok(jobs) if jobs > 0 => Ok(jobs),
+            _ => Err(format!("invalid number of jobs `{value}`")),
+        }
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+            Some("all") => {
+                let release = args.contains("--release");
+                let isolated = args.contains("--isolated");
+                let jobs = args.opt_value_from_fn("--jobs", parse_jobs)?.unwrap_or(1);
+                let limits = parse_limits(&mut args)?;
+                let format = args.opt_value_from_str("--format")?.unwrap_or_default();
+
+                AppArguments::All {
+                    year: parse_year(&mut args)?,
+                    release,
+                    isolated,
+                    jobs,
+                    limits,
+                    format,
+                }
+            }
+            Some("time") if args.contains("--history") => AppArguments::History {
+                puzzle: parse_puzzle(&mut args)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let isolated = args.contains("--isolated");
+                let limits = parse_limits(&mut args)?;
+                let format = args.opt_value_from_str("--format")?.unwrap_or_default();
+                let compare = args.contains("--compare");
+                let threshold = args.opt_value_from_str("--threshold")?.unwrap_or(10.0);
+                let (year, day) = parse_selection(&mut args)?;
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    year,
+                    day,
                     store,
+                    isolated,
+                    limits,
+                    format,
+                    compare_threshold: compare.then_some(threshold),
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+            Some("check-examples") => {
+                let release = args.contains("--release");
+                let isolated = args.contains("--isolated");
+                let limits = parse_limits(&mut args)?;
+                let (year, day) = parse_selection(&mut args)?;
+
+                AppArguments::CheckExamples {
+                    year,
+                    day,
+                    release,
+                    isolated,
+                    limits,
+                }
+            }
+            Some("verify") => {
+                let release = args.contains("--release");
+                let isolated = args.contains("--isolated");
+                let limits = parse_limits(&mut args)?;
+                let (year, day) = parse_selection(&mut args)?;
+
+                AppArguments::Verify {
+                    year,
+                    day,
+                    release,
+                    isolated,
+                    limits,
+                }
+            }
+            Some("download") if args.contains("--all") => AppArguments::DownloadAll {
+                year: parse_year(&mut args)?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
+            Some("download") => {
+                let force = args.contains("--force");
+
+                AppArguments::Download {
+                    puzzle: parse_puzzle(&mut args)?,
+                    force,
+                }
+            }
+            Some("inputs") => match args.free_from_str::<String>()?.as_str() {
+                "encrypt" => AppArguments::EncryptInputs,
+                "decrypt" => AppArguments::DecryptInputs,
+                x => {
+                    eprintln!("Unknown inputs command: {x}. Use `encrypt` or `decrypt`.");
+                    process::exit(1);
+                }
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            Some("read") => AppArguments::Read {
+                puzzle: parse_puzzle(&mut args)?,
             },
+            Some("scaffold") => {
+                let download = args.contains("--download");
+                let overwrite = args.contains("--overwrite");
+                let template = args
+                    .opt_value_from_str("--template")?
+                    .unwrap_or_else(|| DEFAULT_TEMPLATE.into());
+
+                AppArguments::Scaffold {
+                    puzzle: parse_puzzle(&mut args)?,
+                    download,
+                    overwrite,
+                    template,
+                }
+            }
+            Some("solve") if args.contains("--watch") => {
+                let bin = args.opt_value_from_str("--bin")?;
+                let release = args.contains("--release");
+
+                AppArguments::Watch {
+                    puzzle: parse_puzzle(&mut args)?,
+                    bin,
+                    release,
+                }
+            }
+            Some("solve") => {
+                let bin = args.opt_value_from_str("--bin")?;
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let limits = parse_limits(&mut args)?;
+                let format = args.opt_value_from_str("--format")?.unwrap_or_default();
+                let variants = if args.contains("--all-variants") {
+                    Variants::All
+                } else {
+                    args.opt_value_from_str("--variant")?
+                        .map_or(Variants::Default, Variants::Only)
+                };
+                let params = args.values_from_fn("--param", parse_param_override)?;
+
+                AppArguments::Solve {
+                    puzzle: parse_puzzle(&mut args)?,
+                    bin,
+                    release,
+                    dhat,
+                    submit,
+                    limits,
+                    format,
+                    variants,
+                    params,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -102,33 +343,106 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::All {
+                year,
+                release,
+                isolated,
+                jobs,
+                limits,
+                format,
+            } => all::handle(&registry(), year, release, isolated, jobs, limits, format),
+            AppArguments::Time {
+                year,
+                day,
+                all,
+                store,
+                isolated,
+                limits,
+                format,
+                compare_threshold,
+            } => time::handle(
+                &registry(),
+                year,
+                day,
+                all,
+                store,
+                isolated,
+                limits,
+                format,
+                compare_threshold,
+            ),
+            AppArguments::History { puzzle } => time::handle_history(puzzle),
+            AppArguments::CheckExamples {
+                year,
                 day,
+                release,
+                isolated,
+                limits,
+            } => check_examples::handle(&registry(), year, day, release, isolated, limits),
+            AppArguments::Verify {
+                year,
+                day,
+                release,
+                isolated,
+                limits,
+            } => verify::handle(&registry(), year, day, release, isolated, limits),
+            AppArguments::Download { puzzle, force } => download::handle(puzzle, force),
+            AppArguments::DownloadAll { year } => download::handle_all(year),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::EncryptInputs => inputs::handle_encrypt(),
+            AppArguments::DecryptInputs => inputs::handle_decrypt(),
+            AppArguments::Scaffold {
+                puzzle,
                 download,
                 overwrite,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
+                // the puzzle is downloaded first, so its example can be extracted.
                 if download {
-                    download::handle(day);
+                    download::handle_before_scaffold(puzzle);
                 }
+                scaffold::handle(puzzle, overwrite, &template);
             }
+            AppArguments::Watch {
+                puzzle,
+                bin,
+                release,
+            } => watch::handle(puzzle, bin, release),
             AppArguments::Solve {
-                day,
+                puzzle,
+                bin,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                limits,
+                format,
+                variants,
+                params,
+            } => {
+                let mut solution_args = variants.to_args();
+                for (name, value) in params {
+                    solution_args.extend(["--param".into(), format!("{name}={value}")]);
+                }
+                solve::handle(
+                    puzzle,
+                    bin,
+                    release,
+                    dhat,
+                    submit,
+                    limits,
+                    format,
+                    &solution_args,
+                );
+            }
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                match Day::today().map(PuzzleId::from) {
+                    Some(puzzle) => {
+                        let downloaded = download::handle_before_scaffold(puzzle);
+                        scaffold::handle(puzzle, false, DEFAULT_TEMPLATE);
+                        if downloaded {
+                            read::handle(puzzle);
+                        }
                     }
                     None => {
                         eprintln!(
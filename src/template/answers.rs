@@ -0,0 +1,145 @@
+/// Accepted answers of puzzles, used to verify solutions after refactors.
+use std::{collections::HashMap, fs, path::Path, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{answer_from_json, PuzzleId};
+
+/// The accepted answers of a puzzle, recorded in `data/answers/01.json`:
+///
+/// ```json
+/// { "part_1": "11", "part_2": "31" }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    /// The accepted answer of `part`, if recorded.
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part_1 = Some(answer.into()),
+            2 => self.part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.part_1.is_none() && self.part_2.is_none()
+    }
+
+    /// Checks `answer` against the accepted answer of `part`, `None` if no answer is recorded.
+    pub fn verify(&self, part: u8, answer: Option<&str>) -> Option<bool> {
+        self.get(part).map(|expected| answer == Some(expected))
+    }
+
+    /// Reads the answers of a puzzle. If not present, returns empty answers.
+    pub fn read(puzzle: PuzzleId) -> Result<Self, String> {
+        match fs::read_to_string(get_answers_path(puzzle)) {
+            Ok(json) => Answers::try_from(json),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    pub fn store(&self, puzzle: PuzzleId) -> Result<(), String> {
+        let path = get_answers_path(puzzle);
+        if let Some(dir) = Path::new(&path).parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let json = JsonValue::from(self).format().map_err(|e| e.to_string())?;
+        fs::write(path, json + "\n").map_err(|e| e.to_string())
+    }
+}
+
+fn get_answers_path(puzzle: PuzzleId) -> String {
+    format!("./data/answers/{puzzle}.json")
+}
+
+/// Whether any answer of `puzzle` is recorded.
+pub fn has_answers(puzzle: PuzzleId) -> bool {
+    Answers::read(puzzle).is_ok_and(|answers| !answers.is_empty())
+}
+
+/// Records `answer` as the accepted answer of `part`, keeping the answer of the other part.
+pub fn record_answer(puzzle: PuzzleId, part: u8, answer: &str) -> Result<(), String> {
+    let mut answers = Answers::read(puzzle)?;
+    answers.set(part, answer);
+    answers.store(puzzle)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        for (key, answer) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            if let Some(answer) = answer {
+                map.insert(key.into(), JsonValue::String(answer.clone()));
+            }
+        }
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected answers to be a JSON object.")?;
+
+        Ok(Answers {
+            part_1: answer_from_json(json.get("part_1")),
+            part_2: answer_from_json(json.get("part_2")),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_answers() {
+        let mut answers = Answers::default();
+        assert!(answers.is_empty());
+        answers.set(2, "co,de,ka,ta");
+
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    fn reads_numeric_answers() {
+        let answers = Answers::try_from(r#"{ "part_1": 11 }"#.to_string()).unwrap();
+        assert_eq!(answers.get(1), Some("11"));
+        assert_eq!(answers.get(2), None);
+        assert!(Answers::try_from("[]".to_string()).is_err());
+    }
+
+    #[test]
+    fn verifies_answers() {
+        let answers = Answers {
+            part_1: Some("11".into()),
+            part_2: None,
+        };
+        assert_eq!(answers.verify(1, Some("11")), Some(true));
+        assert_eq!(answers.verify(1, Some("12")), Some(false));
+        assert_eq!(answers.verify(1, None), Some(false));
+        assert_eq!(answers.verify(2, Some("31")), None);
+    }
+}
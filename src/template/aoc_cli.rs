@@ -1,10 +1,22 @@
-/// Wrapper module around the "aoc-cli" command-line.
+/// Downloads inputs and puzzle descriptions and submits answers through a [`PuzzleBackend`].
+/// The backend is selected with `AOC_BACKEND`:
+///  1. `native` (default): the built-in [`AocClient`].
+///  2. `aoc-cli`: the external [aoc-cli](https://github.com/scarvalhojr/aoc-cli/) command.
+///  3. `offline`: a directory of fixtures, e.g. for CI.
 use std::{
+    env,
     fmt::Display,
+    fs,
+    path::Path,
     process::{Command, Output, Stdio},
+    time::{SystemTime, UNIX_EPOCH},
 };
 
-use crate::template::Day;
+use crate::template::{
+    aoc_client::{AocClient, AocClientError},
+    encryption::encrypted_path,
+    Answers, PuzzleId,
+};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -16,7 +28,7 @@ pub enum AocCommandError {
 impl Display for AocCommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
+            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment. Try running \"cargo install aoc-cli\" to install it."),
             AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
             AocCommandError::BadExitStatus(_) => {
                 write!(f, "aoc-cli exited with a non-zero status.")
@@ -25,101 +37,544 @@ impl Display for AocCommandError {
     }
 }
 
-pub fn check() -> Result<(), AocCommandError> {
-    Command::new("aoc")
-        .arg("-V")
-        .output()
-        .map_err(|_| AocCommandError::CommandNotFound)?;
-    Ok(())
+#[derive(Debug)]
+pub enum BackendError {
+    /// `AOC_BACKEND` names an unknown backend.
+    UnknownBackend(String),
+    Client(AocClientError),
+    Command(AocCommandError),
+    /// The offline backend has no fixture for the request.
+    Offline(String),
+    /// The puzzle unlocks in this many seconds.
+    Locked(PuzzleId, u64),
+    /// The backend returned something that is not an input, e.g. an error page.
+    InvalidInput(String),
+    Io(String),
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+impl Display for BackendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackendError::UnknownBackend(name) => write!(
+                f,
+                "unknown backend `{name}`, expecting one of `native`, `aoc-cli` or `offline`."
+            ),
+            BackendError::Client(e) => e.fmt(f),
+            BackendError::Command(e) => e.fmt(f),
+            BackendError::Offline(e) => write!(f, "{e}"),
+            BackendError::Locked(puzzle, secs) => write!(
+                f,
+                "day {puzzle} is not unlocked yet, it unlocks in {}d {}h {}m {}s.",
+                secs / 86400,
+                secs % 86400 / 3600,
+                secs % 3600 / 60,
+                secs % 60
+            ),
+            BackendError::InvalidInput(start) => {
+                write!(f, "the downloaded input is not valid: \"{start}\"")
+            }
+            BackendError::Io(e) => write!(f, "could not write file {e}"),
+        }
+    }
+}
 
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
+impl From<AocClientError> for BackendError {
+    fn from(value: AocClientError) -> Self {
+        BackendError::Client(value)
+    }
+}
 
-    call_aoc_cli(&args)
+impl From<AocCommandError> for BackendError {
+    fn from(value: AocCommandError) -> Self {
+        BackendError::Command(value)
+    }
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+/// A source of puzzle inputs and descriptions, and a judge of answers.
+pub trait PuzzleBackend {
+    /// Checks that the backend is usable, e.g. that a session cookie is configured.
+    fn check(&self) -> Result<(), BackendError>;
+
+    /// The personal input of a puzzle.
+    fn input(&self, puzzle: PuzzleId) -> Result<String, BackendError>;
 
-    let args = build_args(
-        "download",
-        &[
-            "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
-        ],
-        day,
-    );
+    /// The description of a puzzle as markdown.
+    fn puzzle(&self, puzzle: PuzzleId) -> Result<String, BackendError>;
+
+    /// Submits an answer and returns the response of the server.
+    fn submit(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Result<String, BackendError>;
+}
+
+/// The backend selected with `AOC_BACKEND`, defaulting to `native`.
+pub fn backend() -> Result<Box<dyn PuzzleBackend>, BackendError> {
+    match env::var("AOC_BACKEND").ok().as_deref() {
+        None | Some("" | "native") => Ok(Box::new(NativeBackend)),
+        Some("aoc-cli") => Ok(Box::new(AocCliBackend::default())),
+        Some("offline") => Ok(Box::new(OfflineBackend::from_env())),
+        Some(name) => Err(BackendError::UnknownBackend(name.into())),
+    }
+}
+
+/// Checks that the selected backend is usable.
+pub fn check() -> Result<(), BackendError> {
+    backend()?.check()
+}
+
+/// Downloads the description of a puzzle to `data/puzzles` and prints it.
+pub fn read(puzzle: PuzzleId) -> Result<(), BackendError> {
+    check_unlocked(puzzle, now())?;
+    let puzzle_path = get_puzzle_path(puzzle);
+
+    let description = backend()?.puzzle(puzzle)?;
+    write_file(&puzzle_path, &description)?;
+    println!("{description}");
+    Ok(())
+}
+
+/// Downloads the input and the description of a puzzle to `data/inputs` and `data/puzzles`.
+/// Files that were already downloaded are kept unless `force` is set, as inputs never change.
+/// Returns whether anything was fetched from the backend.
+pub fn download(puzzle: PuzzleId, force: bool) -> Result<bool, BackendError> {
+    let input_path = get_input_path(puzzle);
+    let puzzle_path = get_puzzle_path(puzzle);
+
+    let has_input = !force && has_valid_input(&input_path);
+    let has_puzzle = !force && has_file(&puzzle_path);
+
+    if has_input && has_puzzle {
+        println!("🎄 Input and puzzle of day {puzzle} are already downloaded, use `--force` to download them again.");
+        return Ok(false);
+    }
+
+    check_unlocked(puzzle, now())?;
+    let backend = backend()?;
+    backend.check()?;
 
-    let output = call_aoc_cli(&args)?;
     println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
+
+    if has_input {
+        println!("🎄 Input was already downloaded to \"{}\".", &input_path);
+    } else {
+        let input = backend.input(puzzle)?;
+        if !is_valid_input(&input) {
+            return Err(BackendError::InvalidInput(
+                input.lines().next().unwrap_or_default().into(),
+            ));
+        }
+        write_file(&input_path, &input)?;
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    }
+
+    if has_puzzle {
+        println!("🎄 Puzzle was already downloaded to \"{}\".", &puzzle_path);
+    } else {
+        write_file(&puzzle_path, &backend.puzzle(puzzle)?)?;
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    }
+
+    Ok(true)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
-    // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
-    args.push(part.to_string());
-    args.push(result.to_string());
-    call_aoc_cli(&args)
+/// Whether the input and the description of a puzzle were already downloaded.
+pub fn is_downloaded(puzzle: PuzzleId) -> bool {
+    has_valid_input(&get_input_path(puzzle)) && has_file(&get_puzzle_path(puzzle))
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+/// Encrypted inputs were valid when they were encrypted, they can't be checked without the key.
+fn has_valid_input(path: &str) -> bool {
+    fs::read_to_string(path).is_ok_and(|x| is_valid_input(&x))
+        || encrypted_path(Path::new(path)).exists()
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+fn has_file(path: &str) -> bool {
+    Path::new(path).exists() || encrypted_path(Path::new(path)).exists()
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
+/// Whether `input` looks like a puzzle input, i.e. it is not empty like a freshly scaffolded input file
+/// and not one of the error messages the server sends instead of an input.
+pub fn is_valid_input(input: &str) -> bool {
+    const ERRORS: [&str; 3] = [
+        "Puzzle inputs differ by user",
+        "Please don't repeatedly request this endpoint before it unlocks",
+        "404 Not Found",
+    ];
+
+    !input.trim().is_empty() && !ERRORS.iter().any(|error| input.starts_with(error))
+}
+
+/// Refuses to fetch puzzles before they unlock, the server answers with errors until then.
+fn check_unlocked(puzzle: PuzzleId, now: u64) -> Result<(), BackendError> {
+    match puzzle.unlocks_at() {
+        Some(unlocks_at) if unlocks_at > now => Err(BackendError::Locked(puzzle, unlocks_at - now)),
+        _ => Ok(()),
+    }
+}
+
+/// Whether `puzzle` is unlocked at the moment.
+pub fn is_unlocked(puzzle: PuzzleId) -> bool {
+    check_unlocked(puzzle, now()).is_ok()
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Submits an answer to `backend`, prints the response of the server and returns it.
+pub fn submit(
+    backend: &dyn PuzzleBackend,
+    puzzle: PuzzleId,
+    part: u8,
+    result: &str,
+) -> Result<String, BackendError> {
+    let message = backend.submit(puzzle, part, result)?;
+    println!("{message}");
+    Ok(message)
+}
+
+fn get_input_path(puzzle: PuzzleId) -> String {
+    format!("data/inputs/{puzzle}.txt")
+}
+
+fn get_puzzle_path(puzzle: PuzzleId) -> String {
+    format!("data/puzzles/{puzzle}.md")
+}
+
+fn write_file(path: &str, contents: &str) -> Result<(), BackendError> {
+    fs::write(path, contents).map_err(|e| BackendError::Io(format!("{path}: {e}")))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A client for a fixed server and session, e.g. a local test server.
+impl PuzzleBackend for AocClient {
+    fn check(&self) -> Result<(), BackendError> {
+        Ok(())
+    }
+
+    fn input(&self, puzzle: PuzzleId) -> Result<String, BackendError> {
+        Ok(AocClient::input(self, puzzle)?)
+    }
+
+    fn puzzle(&self, puzzle: PuzzleId) -> Result<String, BackendError> {
+        Ok(AocClient::puzzle(self, puzzle)?)
+    }
+
+    fn submit(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Result<String, BackendError> {
+        Ok(AocClient::submit(self, puzzle, part, answer)?)
+    }
+}
+
+/// Talks to the website with the built-in [`AocClient`].
+pub struct NativeBackend;
+
+impl PuzzleBackend for NativeBackend {
+    fn check(&self) -> Result<(), BackendError> {
+        AocClient::from_env()?;
+        Ok(())
+    }
+
+    fn input(&self, puzzle: PuzzleId) -> Result<String, BackendError> {
+        Ok(AocClient::from_env()?.input(puzzle)?)
+    }
+
+    fn puzzle(&self, puzzle: PuzzleId) -> Result<String, BackendError> {
+        Ok(AocClient::from_env()?.puzzle(puzzle)?)
+    }
+
+    fn submit(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Result<String, BackendError> {
+        Ok(AocClient::from_env()?.submit(puzzle, part, answer)?)
     }
 }
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+/* -------------------------------------------------------------------------- */
+
+/// Wraps the "aoc-cli" command-line, which has to be installed separately.
+pub struct AocCliBackend {
+    command: String,
+}
+
+impl AocCliBackend {
+    /// Calls the aoc-cli executable at `command` instead of `aoc` from `PATH`.
+    pub fn new(command: &str) -> Self {
+        Self {
+            command: command.into(),
+        }
+    }
+}
+
+impl Default for AocCliBackend {
+    fn default() -> Self {
+        Self::new("aoc")
+    }
+}
+
+impl PuzzleBackend for AocCliBackend {
+    fn check(&self) -> Result<(), BackendError> {
+        Command::new(&self.command)
+            .arg("-V")
+            .output()
+            .map_err(|_| AocCommandError::CommandNotFound)?;
+        Ok(())
+    }
+
+    fn input(&self, puzzle: PuzzleId) -> Result<String, BackendError> {
+        self.download_to_temp_file(puzzle, "--input-only", "--input-file")
+    }
+
+    fn puzzle(&self, puzzle: PuzzleId) -> Result<String, BackendError> {
+        self.download_to_temp_file(puzzle, "--puzzle-only", "--puzzle-file")
+    }
+
+    fn submit(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Result<String, BackendError> {
+        // workaround: the argument order is inverted for submit.
+        let mut args = build_args("submit", &[], puzzle);
+        args.push(part.to_string());
+        args.push(answer.to_string());
+
+        // aoc-cli reports wrong answers with a non-zero status, their message is still a valid response.
+        let output = match self.call(&args) {
+            Ok(output) | Err(AocCommandError::BadExitStatus(output)) => output,
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+impl AocCliBackend {
+    /// aoc-cli only writes downloads to files, so they are downloaded to a temporary file and read back.
+    fn download_to_temp_file(
+        &self,
+        puzzle: PuzzleId,
+        only_flag: &str,
+        file_flag: &str,
+    ) -> Result<String, BackendError> {
+        let name = format!("aoc-{}-{puzzle}{only_flag}", std::process::id());
+        let path = env::temp_dir().join(name);
+        let path_str = path.to_string_lossy().to_string();
+
+        let args = build_args(
+            "download",
+            &[
+                "--overwrite".into(),
+                only_flag.into(),
+                file_flag.into(),
+                path_str.clone(),
+            ],
+            puzzle,
+        );
+
+        let output = self.call(&args);
+        let contents = fs::read_to_string(&path);
+        let _ = fs::remove_file(&path);
+
+        output?;
+        contents.map_err(|e| BackendError::Io(format!("{path_str}: {e}")))
+    }
+
+    /// Calls aoc-cli, capturing stdout so it can be returned.
+    fn call(&self, args: &[String]) -> Result<Output, AocCommandError> {
+        let output = Command::new(&self.command)
+            .args(args)
+            .stderr(Stdio::inherit())
+            .output()
+            .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+        if output.status.success() {
+            Ok(output)
+        } else {
+            Err(AocCommandError::BadExitStatus(output))
+        }
+    }
+}
+
+fn build_args(command: &str, args: &[String], puzzle: PuzzleId) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
+    if let Some(year) = puzzle.year_or_default() {
         cmd_args.push("--year".into());
         cmd_args.push(year.to_string());
     }
 
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+    cmd_args.append(&mut vec![
+        "--day".into(),
+        puzzle.day.to_string(),
+        command.into(),
+    ]);
 
     cmd_args
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
-    // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
-        .args(args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .output()
-        .map_err(|_| AocCommandError::CommandNotCallable)?;
+/* -------------------------------------------------------------------------- */
 
-    if output.status.success() {
-        Ok(output)
-    } else {
-        Err(AocCommandError::BadExitStatus(output))
+/// Serves inputs and puzzles from a directory of fixtures, `AOC_OFFLINE_DIR` or `data/offline` by default.
+/// The directory is laid out like `data`, i.e. `inputs/01.txt` and `puzzles/01.md`.
+/// Submissions are judged against the answers in `answers/01.json`, with the messages of the website.
+pub struct OfflineBackend {
+    dir: String,
+}
+
+impl OfflineBackend {
+    pub fn new(dir: &str) -> Self {
+        Self {
+            dir: dir.trim_end_matches('/').into(),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(
+            &env::var("AOC_OFFLINE_DIR")
+                .ok()
+                .filter(|x| !x.is_empty())
+                .unwrap_or_else(|| "data/offline".into()),
+        )
+    }
+
+    fn read_fixture(&self, path: &str) -> Result<String, BackendError> {
+        let path = format!("{}/{path}", self.dir);
+        fs::read_to_string(&path)
+            .map_err(|_| BackendError::Offline(format!("no offline fixture at `{path}`.")))
+    }
+}
+
+impl PuzzleBackend for OfflineBackend {
+    fn check(&self) -> Result<(), BackendError> {
+        fs::metadata(&self.dir).map(|_| ()).map_err(|_| {
+            BackendError::Offline(format!("offline directory `{}` not found.", self.dir))
+        })
+    }
+
+    fn input(&self, puzzle: PuzzleId) -> Result<String, BackendError> {
+        self.read_fixture(&format!("inputs/{puzzle}.txt"))
+    }
+
+    fn puzzle(&self, puzzle: PuzzleId) -> Result<String, BackendError> {
+        self.read_fixture(&format!("puzzles/{puzzle}.md"))
+    }
+
+    fn submit(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Result<String, BackendError> {
+        let answers = Answers::try_from(self.read_fixture(&format!("answers/{puzzle}.json"))?)
+            .map_err(BackendError::Offline)?;
+
+        let Some(expected) = answers.get(part) else {
+            return Err(BackendError::Offline(format!(
+                "no offline answer for part {part} of day {puzzle}."
+            )));
+        };
+
+        let message = if answer == expected {
+            "That's the right answer!"
+        } else {
+            match (answer.parse::<i128>(), expected.parse::<i128>()) {
+                (Ok(answer), Ok(expected)) if answer > expected => {
+                    "That's not the right answer; your answer is too high."
+                }
+                (Ok(answer), Ok(expected)) if answer < expected => {
+                    "That's not the right answer; your answer is too low."
+                }
+                _ => "That's not the right answer.",
+            }
+        };
+
+        Ok(message.into())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs};
+
+    use super::{check_unlocked, is_valid_input, AocCliBackend, OfflineBackend, PuzzleBackend};
+    use crate::{day, template::PuzzleId, year};
+
+    fn puzzle() -> PuzzleId {
+        PuzzleId::new(Some(year!(2024)), day!(1))
+    }
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn validates_inputs() {
+        assert!(is_valid_input("1 2\n"));
+        assert!(!is_valid_input(""));
+        assert!(!is_valid_input("\n"));
+        assert!(!is_valid_input(
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+        ));
+    }
+
+    #[test]
+    fn refuses_locked_puzzles() {
+        let unlocks_at = puzzle().unlocks_at().unwrap();
+        assert!(check_unlocked(puzzle(), unlocks_at - 1).is_err());
+        assert!(check_unlocked(puzzle(), unlocks_at).is_ok());
+    }
+
+    #[test]
+    fn serves_offline_fixtures() {
+        let dir = temp_dir("offline");
+        fs::create_dir_all(dir.join("inputs")).unwrap();
+        fs::create_dir_all(dir.join("answers")).unwrap();
+        fs::write(dir.join("inputs/2024-01.txt"), "1 2\n").unwrap();
+        fs::write(dir.join("answers/2024-01.json"), r#"{ "part_1": 42 }"#).unwrap();
+
+        let backend = OfflineBackend::new(&dir.to_string_lossy());
+        assert!(backend.check().is_ok());
+        assert_eq!(backend.input(puzzle()).unwrap(), "1 2\n");
+        assert!(backend.puzzle(puzzle()).is_err());
+        assert!(backend
+            .submit(puzzle(), 1, "42")
+            .unwrap()
+            .contains("That's the right answer"));
+        assert!(backend
+            .submit(puzzle(), 1, "43")
+            .unwrap()
+            .contains("too high"));
+        assert!(backend
+            .submit(puzzle(), 1, "x")
+            .unwrap()
+            .contains("not the right answer"));
+        assert!(backend.submit(puzzle(), 2, "42").is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn calls_aoc_cli() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // a fake `aoc` executable, which writes downloads to the file following `--input-file`.
+        let dir = temp_dir("fake-aoc");
+        let aoc = dir.join("aoc");
+        fs::write(
+            &aoc,
+            r#"#!/bin/sh
+case "$*" in
+    *submit*) echo "That's not the right answer; your answer is too low. ($*)"; exit 1 ;;
+    *--input-file*) while [ "$1" != "--input-file" ]; do shift; done; printf '1 2\n' > "$2" ;;
+esac
+"#,
+        )
+        .unwrap();
+        fs::set_permissions(&aoc, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let backend = AocCliBackend::new(&aoc.to_string_lossy());
+        assert_eq!(backend.input(puzzle()).unwrap(), "1 2\n");
+        assert_eq!(
+            backend.submit(puzzle(), 2, "7").unwrap(),
+            "That's not the right answer; your answer is too low. (--year 2024 --day 01 submit 2 7)\n"
+        );
+
+        fs::remove_dir_all(dir).unwrap();
     }
 }
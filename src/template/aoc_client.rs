@@ -0,0 +1,266 @@
+/// Built-in client for the Advent of Code website.
+use std::{env, fmt::Display, fs, path::PathBuf, time::Duration};
+
+use crate::template::{html::articles_to_markdown, PuzzleId};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie in `AOC_SESSION` or `~/.adventofcode.session`.
+    SessionNotFound,
+    /// The puzzle has no year and `AOC_YEAR` is not set.
+    YearNotFound,
+    /// The server answered with an error status, e.g. `404` for puzzles that are not unlocked yet.
+    BadStatus(u16, String),
+    /// The server could not be reached.
+    Transport(String),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or create the file `~/.adventofcode.session`."
+            ),
+            AocClientError::YearNotFound => {
+                write!(f, "no year given and `AOC_YEAR` is not set.")
+            }
+            AocClientError::BadStatus(status, body) => {
+                write!(f, "the server responded with status {status}: {}", body.trim())
+            }
+            AocClientError::Transport(e) => write!(f, "could not reach the server: {e}"),
+        }
+    }
+}
+
+/// Client for the Advent of Code website, authenticated with a session cookie.
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .redirects(0)
+                .build(),
+        }
+    }
+
+    /// Creates a client for the website, or for the server at `AOC_BASE_URL` if set.
+    /// The session cookie is read from `AOC_SESSION`, falling back to the file `~/.adventofcode.session`.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL")
+            .ok()
+            .filter(|x| !x.is_empty())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.into());
+
+        let session = env::var("AOC_SESSION")
+            .ok()
+            .or_else(|| get_session_path().and_then(|path| fs::read_to_string(path).ok()))
+            .filter(|x| !x.trim().is_empty())
+            .ok_or(AocClientError::SessionNotFound)?;
+
+        Ok(Self::new(&base_url, &session))
+    }
+
+    /// Downloads the personal input of a puzzle.
+    pub fn input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.puzzle_url(puzzle)?);
+        self.send(self.agent.get(&url), None)
+    }
+
+    /// Downloads the description of a puzzle as markdown, including part two once it is unlocked.
+    pub fn puzzle(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let html = self.send(self.agent.get(&self.puzzle_url(puzzle)?), None)?;
+        Ok(articles_to_markdown(&html))
+    }
+
+    /// Submits an answer and returns the message of the server as markdown.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<String, AocClientError> {
+        let url = format!("{}/answer", self.puzzle_url(puzzle)?);
+        let level = part.to_string();
+        let html = self.send(
+            self.agent.post(&url),
+            Some(&[("level", level.as_str()), ("answer", answer)]),
+        )?;
+        Ok(articles_to_markdown(&html))
+    }
+
+    fn puzzle_url(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let year = puzzle
+            .year_or_default()
+            .ok_or(AocClientError::YearNotFound)?;
+        Ok(format!(
+            "{}/{year}/day/{}",
+            self.base_url,
+            puzzle.day.into_inner()
+        ))
+    }
+
+    fn send(
+        &self,
+        request: ureq::Request,
+        form: Option<&[(&str, &str)]>,
+    ) -> Result<String, AocClientError> {
+        let request = request.set("Cookie", &format!("session={}", self.session));
+
+        let response = match form {
+            Some(form) => request.send_form(form),
+            None => request.call(),
+        };
+
+        match response {
+            // the server redirects instead of answering if the session is not valid.
+            Ok(response) if response.status() >= 300 => Err(AocClientError::BadStatus(
+                response.status(),
+                "redirected, is the session cookie still valid?".into(),
+            )),
+            Ok(response) => response
+                .into_string()
+                .map_err(|e| AocClientError::Transport(e.to_string())),
+            Err(ureq::Error::Status(status, response)) => Err(AocClientError::BadStatus(
+                status,
+                response.into_string().unwrap_or_default(),
+            )),
+            Err(e) => Err(AocClientError::Transport(e.to_string())),
+        }
+    }
+}
+
+fn get_session_path() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".adventofcode.session"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+pub(crate) mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::{AocClient, AocClientError};
+    use crate::{day, template::PuzzleId, year};
+
+    /// Serves `responses` to consecutive requests on a local port and returns its base url.
+    /// The requests are sent back through the returned receiver, as request line, headers and body.
+    pub(crate) fn mock_server(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, std::sync::mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body_bytes = vec![0; content_length];
+                reader.read_exact(&mut body_bytes).unwrap();
+                request.push_str(&String::from_utf8_lossy(&body_bytes));
+                let _ = sender.send(request);
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (base_url, receiver)
+    }
+
+    fn puzzle() -> PuzzleId {
+        PuzzleId::new(Some(year!(2024)), day!(1))
+    }
+
+    #[test]
+    fn downloads_inputs() {
+        let (base_url, requests) = mock_server(vec![(200, "3   4\n4   3\n")]);
+        let client = AocClient::new(&base_url, "abc\n");
+
+        assert_eq!(client.input(puzzle()).unwrap(), "3   4\n4   3\n");
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input HTTP/1.1"));
+        assert!(request.contains("Cookie: session=abc\r\n"));
+    }
+
+    #[test]
+    fn downloads_puzzles() {
+        let (base_url, _) = mock_server(vec![(
+            200,
+            "<main><article class=\"day-desc\"><h2>--- Day 1 ---</h2><p>Hi.</p></article></main>",
+        )]);
+        let client = AocClient::new(&base_url, "abc");
+
+        assert_eq!(
+            client.puzzle(puzzle()).unwrap(),
+            "## --- Day 1 ---\n\nHi.\n"
+        );
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, requests) = mock_server(vec![(
+            200,
+            "<main><article><p>That's the right answer!</p></article></main>",
+        )]);
+        let client = AocClient::new(&base_url, "abc");
+
+        assert_eq!(
+            client.submit(puzzle(), 2, "a,b").unwrap(),
+            "That's the right answer!\n"
+        );
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2024/day/1/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=a%2Cb"));
+    }
+
+    #[test]
+    fn errors_for_bad_statuses() {
+        let (base_url, _) = mock_server(vec![(404, "Not found")]);
+        let client = AocClient::new(&base_url, "abc");
+
+        assert!(matches!(
+            client.input(puzzle()),
+            Err(AocClientError::BadStatus(404, _))
+        ));
+    }
+}
@@ -1,5 +1,31 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    all_days, limits::Limits, run_multi::run_multi, OutputFormat, PuzzleId, Registry, Year,
+};
+
+/// Run every day of `year`, or of the default year if `None`, running up to `jobs` days at once.
+pub fn handle(
+    registry: &Registry,
+    year: Option<Year>,
+    is_release: bool,
+    is_isolated: bool,
+    jobs: usize,
+    limits: Limits,
+    format: OutputFormat,
+) {
+    let (summary, _) = run_multi(
+        registry,
+        &all_days().map(|day| PuzzleId::new(year, day)).collect(),
+        is_release,
+        false,
+        is_isolated,
+        jobs,
+        limits,
+        format,
+    );
+
+    if summary.has_failures() {
+        process::exit(1);
+    }
 }
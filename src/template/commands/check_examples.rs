@@ -0,0 +1,45 @@
+use super::{check_answers, Check};
+use crate::template::{
+    all_days, has_examples_manifest, limits::Limits, read_examples, Day, PuzzleId, Registry, Year,
+};
+
+/// Check the solutions of `year` against the expected answers in their examples manifests.
+/// Checks every day with a manifest if `day` is `None`. Exits with a non-zero status if any answer is wrong.
+pub fn handle(
+    registry: &Registry,
+    year: Option<Year>,
+    day: Option<Day>,
+    is_release: bool,
+    is_isolated: bool,
+    limits: Limits,
+) {
+    let puzzles: Vec<PuzzleId> = match day {
+        Some(day) => vec![PuzzleId::new(year, day)],
+        None => all_days()
+            .map(|day| PuzzleId::new(year, day))
+            .filter(|puzzle| has_examples_manifest(*puzzle))
+            .collect(),
+    };
+
+    // variants are checked along with the default implementations, they have to give the same answers.
+    check_answers(
+        registry,
+        &puzzles,
+        is_release,
+        is_isolated,
+        limits,
+        ("Examples", "passed"),
+        |puzzle| {
+            let examples =
+                read_examples(puzzle).map_err(|e| format!("Could not read examples: {e}"))?;
+
+            Ok(examples
+                .into_iter()
+                .map(|example| Check {
+                    expected: [1, 2].map(|part| example.expected(part).map(String::from)),
+                    example: Some(example),
+                })
+                .collect())
+        },
+    );
+}
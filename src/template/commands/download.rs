@@ -1,14 +1,53 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{all_days, aoc_cli, PuzzleId, Year};
+use std::{process, thread, time::Duration};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+/// Pause between two downloads of `--all`, to go easy on the servers.
+const DOWNLOAD_DELAY: Duration = Duration::from_secs(3);
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+pub fn handle(puzzle: PuzzleId, force: bool) {
+    if let Err(e) = aoc_cli::download(puzzle, force) {
+        eprintln!("failed to download puzzle: {e}");
         process::exit(1);
     };
 }
+
+/// Downloads a puzzle before it is scaffolded, so its example can be extracted.
+/// Unlike [`handle`], a failed download only warns, the day is then scaffolded with an empty example.
+/// Returns whether the puzzle was downloaded.
+pub fn handle_before_scaffold(puzzle: PuzzleId) -> bool {
+    match aoc_cli::download(puzzle, false) {
+        Ok(_) => true,
+        Err(e) => {
+            eprintln!(
+                "Warning: failed to download puzzle, scaffolding it without its example: {e}"
+            );
+            false
+        }
+    }
+}
+
+/// Downloads the inputs and puzzles of every unlocked day of `year` that were not downloaded yet.
+pub fn handle_all(year: Option<Year>) {
+    let mut fetched_before = false;
+
+    for day in all_days() {
+        let puzzle = PuzzleId::new(year, day);
+
+        if !aoc_cli::is_unlocked(puzzle) {
+            println!("🎄 Day {puzzle} is not unlocked yet, stopping.");
+            break;
+        }
+
+        if fetched_before && !aoc_cli::is_downloaded(puzzle) {
+            thread::sleep(DOWNLOAD_DELAY);
+        }
+
+        match aoc_cli::download(puzzle, false) {
+            Ok(fetched) => fetched_before = fetched,
+            Err(e) => {
+                eprintln!("failed to download puzzle {puzzle}: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
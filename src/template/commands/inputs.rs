@@ -0,0 +1,90 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::encryption::{decrypt_file, encrypted_path, Key, ENCRYPTED_SUFFIX};
+
+/// Folders in `data` holding personal puzzle data that must not be redistributed.
+/// The answers in `data/answers` and the log `data/submissions.jsonl` are out of scope: they are written on every
+/// submission and read as plain files.
+const FOLDERS: [&str; 2] = ["data/inputs", "data/puzzles"];
+
+/// Encrypt every downloaded input and puzzle description to a sibling `.enc` file that can be committed.
+pub fn handle_encrypt() {
+    let key = read_key();
+    let mut count = 0;
+
+    for path in files(|path| path.extension().is_some_and(|x| x != ENCRYPTED_SUFFIX)) {
+        let Ok(contents) = fs::read(&path) else {
+            eprintln!("Could not read \"{}\".", path.display());
+            process::exit(1);
+        };
+
+        // scaffolded inputs that were not downloaded yet.
+        if contents.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+
+        let encrypted = encrypted_path(&path);
+        write_file(&encrypted, &key.encrypt(&contents));
+        println!("🔒 Encrypted \"{}\".", encrypted.display());
+        count += 1;
+    }
+
+    println!("🔒 Encrypted {count} file(s).");
+}
+
+/// Decrypt every `.enc` file in `data/inputs` and `data/puzzles` next to it, overwriting the plain files.
+pub fn handle_decrypt() {
+    let key = read_key();
+    let mut count = 0;
+
+    for path in files(|path| path.extension().is_some_and(|x| x == ENCRYPTED_SUFFIX)) {
+        let contents = match decrypt_file(&key, &path) {
+            Ok(contents) => contents,
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        };
+
+        let decrypted = path.with_extension("");
+        write_file(&decrypted, contents.as_bytes());
+        println!("🔓 Decrypted \"{}\".", decrypted.display());
+        count += 1;
+    }
+
+    println!("🔓 Decrypted {count} file(s).");
+}
+
+fn read_key() -> Key {
+    match Key::from_env() {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn files(filter: impl Fn(&Path) -> bool) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = FOLDERS
+        .iter()
+        .filter_map(|folder| fs::read_dir(folder).ok())
+        .flatten()
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.is_file() && filter(path))
+        .collect();
+
+    files.sort();
+    files
+}
+
+fn write_file(path: &Path, contents: &[u8]) {
+    if let Err(e) = fs::write(path, contents) {
+        eprintln!("Could not write \"{}\": {e}", path.display());
+        process::exit(1);
+    }
+}
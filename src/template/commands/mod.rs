@@ -1,6 +1,303 @@
 pub mod all;
+pub mod check_examples;
 pub mod download;
+pub mod inputs;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+pub mod verify;
+pub mod watch;
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    process,
+    time::Duration,
+};
+
+use crate::template::{
+    limits::Limits,
+    run_multi::{build_runs, child_commands, get_bin_name, get_runs},
+    try_read_file, Example, PartRecord, PuzzleId, Registry, Solution, ANSI_BOLD, ANSI_RESET,
+};
+
+/// An input to check the solutions of a puzzle against, together with the answers they have to give.
+struct Check {
+    /// The example to solve, `None` for the puzzle input.
+    example: Option<Example>,
+    /// The expected answers of part 1 and part 2, if known.
+    expected: [Option<String>; 2],
+}
+
+impl Check {
+    fn expected(&self, part: u8) -> Option<&str> {
+        self.expected
+            .get(usize::from(part).checked_sub(1)?)?
+            .as_deref()
+    }
+
+    fn has_expected(&self) -> bool {
+        self.expected.iter().any(Option::is_some)
+    }
+}
+
+/// Runs the binaries solving `puzzles`, extra binaries included, against the checks returned by `get_checks`
+/// and compares the answers of every part and variant to the expected ones.
+///
+/// Like `cargo all`, solutions run in-process unless `is_isolated` or `limits` are set or the `registry` does not
+/// hold compiled solutions. Child processes are built
+/// with a single `cargo build` up front, parts exceeding `limits` are killed and count as wrong answers.
+/// Prints a summary of `(title, passed_label)`, e.g. `Examples: 3 passed, 0 failed`, and exits with a non-zero
+/// status if any answer is wrong.
+fn check_answers(
+    registry: &Registry,
+    puzzles: &[PuzzleId],
+    is_release: bool,
+    is_isolated: bool,
+    limits: Limits,
+    (title, passed_label): (&str, &str),
+    get_checks: impl Fn(PuzzleId) -> Result<Vec<Check>, String>,
+) {
+    let is_isolated = is_isolated || limits.is_set() || !registry.is_in_process();
+    let runs = get_runs(registry, puzzles);
+    let builds = if is_isolated {
+        build_runs(&runs, is_release)
+    } else {
+        child_commands::Builds::default()
+    };
+
+    let (mut passed, mut failed) = (0, 0);
+
+    for (i, &(puzzle, solution, bin)) in runs.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        match bin {
+            Some(bin) => println!("{ANSI_BOLD}Day {puzzle}{ANSI_RESET} (extra: {bin})"),
+            None => println!("{ANSI_BOLD}Day {puzzle}{ANSI_RESET}"),
+        }
+        println!("------");
+
+        let bin_name = get_bin_name(puzzle, bin);
+
+        if builds.is_failed(&bin_name) {
+            println!("✘ Could not be built, see the errors above.");
+            failed += 1;
+            continue;
+        }
+
+        let executable = builds.executable(&bin_name);
+        if (is_isolated && executable.is_none()) || (!is_isolated && solution.is_none()) {
+            println!("Not solved.");
+            continue;
+        }
+
+        let checks = match get_checks(puzzle) {
+            Ok(checks) => checks,
+            Err(e) => {
+                eprintln!("{e}");
+                failed += 1;
+                continue;
+            }
+        };
+
+        if !checks.iter().any(Check::has_expected) {
+            println!("No expected answers.");
+            continue;
+        }
+
+        for check in checks.iter().filter(|check| check.has_expected()) {
+            let records = match (executable, solution) {
+                (Some(executable), _) => {
+                    let example = check.example.as_ref().map(|example| example.name.as_str());
+                    child_commands::check_solution(
+                        puzzle, bin, solution, executable, example, limits,
+                    )
+                    .map(Option::unwrap_or_default)
+                    .map_err(|e| format!("✘ Failed to run the solution: {e:?}"))
+                }
+                (None, Some(solution)) => solve_in_process(solution, puzzle, bin, check),
+                (None, None) => unreachable!("unsolved days are skipped"),
+            };
+
+            match records {
+                Ok(records) => {
+                    let (check_passed, check_failed) = compare_answers(check, &records);
+                    passed += check_passed;
+                    failed += check_failed;
+                }
+                Err(e) => {
+                    eprintln!("{e}");
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    println!();
+    println!("{ANSI_BOLD}{title}:{ANSI_RESET} {passed} {passed_label}, {failed} failed");
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+/// Solves every part and variant of `solution` in-process against the input of `check`.
+fn solve_in_process(
+    solution: &dyn Solution,
+    puzzle: PuzzleId,
+    bin: Option<&str>,
+    check: &Check,
+) -> Result<Vec<PartRecord>, String> {
+    let (input, params) = match &check.example {
+        Some(example) => (example.input.clone(), example.params.as_slice()),
+        None => (
+            try_read_file("inputs", puzzle).map_err(|e| format!("Could not read input: {e}"))?,
+            &[][..],
+        ),
+    };
+
+    let parts = solution.parts().iter().map(|&part| (None, part));
+    let variants = solution
+        .variants()
+        .iter()
+        .map(|&(variant, part)| (Some(variant), part));
+
+    Ok(parts
+        .chain(variants)
+        .map(|(variant, part)| {
+            let answer = solve_caught(solution, variant, part, &input, params);
+            PartRecord::new(puzzle, part, answer, Duration::ZERO, 1)
+                .with_bin(bin)
+                .with_variant(variant)
+        })
+        .collect())
+}
+
+/// Prints whether the answers of `records` match the expected answers of `check`.
+/// Returns the number of correct and wrong answers.
+fn compare_answers(check: &Check, records: &[PartRecord]) -> (usize, usize) {
+    let (mut passed, mut failed) = (0, 0);
+
+    let prefix = match &check.example {
+        Some(example) => format!("{} ", example.name),
+        None => String::new(),
+    };
+
+    // a run that reported nothing, e.g. because it crashed, did not give any of the expected answers.
+    if records.is_empty() {
+        for (part, expected) in [1, 2].map(|part| (part, check.expected(part))) {
+            if let Some(expected) = expected {
+                failed += 1;
+                println!("{prefix}Part {part}: ✘ expected {expected}, got nothing");
+            }
+        }
+    }
+
+    for record in records {
+        let label = record.part_label();
+
+        // the parts can't run without their parsed input.
+        if record.is_parse_step() {
+            if let Some(failure) = record.failure() {
+                failed += 1;
+                println!("{prefix}{label}: ✘ {failure}");
+            }
+            continue;
+        }
+
+        let Some(expected) = check.expected(record.part) else {
+            continue;
+        };
+
+        match (&record.answer, record.failure()) {
+            (Some(answer), _) if answer == expected => {
+                passed += 1;
+                println!("{prefix}{label}: ✔ {answer}");
+            }
+            (Some(answer), _) => {
+                failed += 1;
+                println!("{prefix}{label}: ✘ expected {expected}, got {answer}");
+            }
+            (None, Some(failure)) => {
+                failed += 1;
+                println!("{prefix}{label}: ✘ expected {expected}, {failure}");
+            }
+            (None, None) => {
+                failed += 1;
+                println!("{prefix}{label}: ✘ expected {expected}, got nothing");
+            }
+        }
+    }
+
+    (passed, failed)
+}
+
+/// Solve a part in-process, treating a panicking solution like an unsolved part.
+fn solve_caught(
+    solution: &dyn Solution,
+    variant: Option<&str>,
+    part: u8,
+    input: &str,
+    params: &[(String, String)],
+) -> Option<String> {
+    let params = match solution.params(params) {
+        Ok(params) => params,
+        Err(e) => {
+            eprintln!("Invalid parameters: {e}");
+            return None;
+        }
+    };
+
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        solution.solve(variant, part, input, params.as_ref())
+    }))
+    .ok()
+    .flatten()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{compare_answers, Check};
+    use crate::{
+        day,
+        template::{limits::LimitExceeded, PartRecord},
+    };
+
+    #[test]
+    fn compares_answers() {
+        let check = Check {
+            example: None,
+            expected: [Some("11".into()), Some("31".into())],
+        };
+
+        let records = [
+            PartRecord::new(day!(1), 1, Some("11".into()), Duration::ZERO, 1),
+            PartRecord::new(day!(1), 2, Some("30".into()), Duration::ZERO, 1),
+            PartRecord::new(day!(1), 1, Some("11".into()), Duration::ZERO, 1)
+                .with_variant(Some("fast")),
+            PartRecord::killed(
+                day!(1),
+                2,
+                LimitExceeded::Timeout(Duration::from_secs(1)),
+                Duration::from_secs(1),
+            )
+            .with_variant(Some("fast")),
+        ];
+
+        assert_eq!(compare_answers(&check, &records), (2, 2));
+    }
+
+    #[test]
+    fn fails_runs_without_records() {
+        let check = Check {
+            example: None,
+            expected: [Some("11".into()), None],
+        };
+
+        assert_eq!(compare_answers(&check, &[]), (0, 1));
+    }
+}
@@ -1,13 +1,15 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
-
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+use crate::template::{
+    encryption, extract_example, has_examples_manifest,
+    templates::{load_template, render_template, TemplateContext},
+    write_examples_manifest, PuzzleId,
+};
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -27,10 +29,20 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Scaffold a solution for `puzzle` from the template `template`, see [`load_template`].
+/// If the puzzle was downloaded before, its example and answers are extracted into `data/examples`.
+/// Inputs and examples that already have contents are kept, as are inputs that are only present encrypted.
+pub fn handle(puzzle: PuzzleId, overwrite: bool, template: &str) {
+    let input_path = format!("data/inputs/{puzzle}.txt");
+    let module_path = format!("src/bin/{puzzle}.rs");
+
+    let template = match load_template(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to load template: {e}.");
+            process::exit(1);
+        }
+    };
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,11 +52,10 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    // the examples are written first, so the generated tests can assert their answers.
+    let example_messages = create_examples(puzzle);
+
+    match file.write_all(render_template(&template, &TemplateContext::read(puzzle)).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -54,26 +65,93 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    let encrypted_input_path = encryption::encrypted_path(Path::new(&input_path));
+
+    if has_contents(&input_path) {
+        println!("Kept existing input file \"{}\"", &input_path);
+    } else if encrypted_input_path.exists() {
+        // the input is decrypted when read, an empty plain file would only clutter the directory.
+        println!(
+            "Kept encrypted input file \"{}\"",
+            encrypted_input_path.display()
+        );
+    } else {
+        match create_file(&input_path) {
+            Ok(_) => {
+                println!("Created empty input file \"{}\"", &input_path);
+            }
+            Err(e) => {
+                eprintln!("Failed to create input file: {e}");
+                process::exit(1);
+            }
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+    for message in example_messages {
+        println!("{message}");
+    }
+
+    println!("---");
+    match puzzle.year {
+        Some(year) => println!(
+            "🎄 Type `cargo solve {year} {}` to run your solution.",
+            puzzle.day
+        ),
+        None => println!("🎄 Type `cargo solve {}` to run your solution.", puzzle.day),
+    }
+}
+
+/// Creates the example file, from the downloaded puzzle description if possible, and an examples manifest
+/// holding the answers found in the description. Returns the messages to print.
+fn create_examples(puzzle: PuzzleId) -> Vec<String> {
+    let example_path = format!("data/examples/{puzzle}.txt");
+
+    if has_contents(&example_path) {
+        return vec![format!("Kept existing example file \"{example_path}\"")];
+    }
+
+    let extracted = encryption::read_to_string(format!("data/puzzles/{puzzle}.md"))
+        .ok()
+        .and_then(|description| extract_example(puzzle, &description));
+
+    let Some(example) = extracted else {
+        return match create_file(&example_path) {
+            Ok(_) => vec![format!("Created empty example file \"{example_path}\"")],
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
+        };
+    };
+
+    if let Err(e) = fs::write(&example_path, format!("{}\n", example.input)) {
+        eprintln!("Failed to write example file: {e}");
+        process::exit(1);
+    }
+
+    let mut messages = vec![format!(
+        "Created example file \"{example_path}\" from the puzzle description"
+    )];
+
+    if (example.part_1.is_some() || example.part_2.is_some()) && !has_examples_manifest(puzzle) {
+        if let Err(e) = write_examples_manifest(puzzle, &example) {
+            eprintln!("Failed to write examples manifest: {e}");
             process::exit(1);
         }
+
+        let answers: Vec<String> = [1, 2]
+            .into_iter()
+            .filter_map(|part| Some(format!("part {part}: {}", example.expected(part)?)))
+            .collect();
+        messages.push(format!(
+            "Created examples manifest \"data/examples/{puzzle}.json\" ({}), please double-check the answers",
+            answers.join(", ")
+        ));
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    messages
+}
+
+fn has_contents(path: &str) -> bool {
+    fs::read_to_string(path).is_ok_and(|x| !x.trim().is_empty())
 }
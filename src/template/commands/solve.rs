@@ -1,9 +1,27 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{
+    limits::Limits, records_to_json, run_multi::child_commands, OutputFormat, PuzzleId,
+};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Run the solution of `puzzle`, or the extra binary `bin` solving it, as its own binary.
+/// `solution_args` are forwarded to the solution, e.g. `--variant fast` or `--param rows=7`.
+/// The solution is killed once it exceeds `limits`, the part it was working on is reported as timed out or
+/// out of memory. Exits with a non-zero status if a part failed.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    puzzle: PuzzleId,
+    bin: Option<String>,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    limits: Limits,
+    format: OutputFormat,
+    solution_args: &[String],
+) {
+    let extra_bin = bin.filter(|bin| *bin != puzzle.to_string());
+    let bin = extra_bin.clone().unwrap_or_else(|| puzzle.to_string());
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), bin];
 
     if dhat {
         cmd_args.extend([
@@ -16,6 +34,19 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    // build first, so compiling does not count towards the timeout.
+    if limits.is_set() {
+        let built = Command::new("cargo")
+            .arg("build")
+            .args(&cmd_args[1..])
+            .status()
+            .is_ok_and(|status| status.success());
+
+        if !built {
+            process::exit(1);
+        }
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -23,6 +54,46 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    cmd_args.extend_from_slice(solution_args);
+
+    if limits.is_set() {
+        // the solution reports its records, so the part that exceeded a limit can be reported in any format.
+        cmd_args.push("--format".to_string());
+        cmd_args.push(OutputFormat::Ndjson.to_string());
+
+        let mut command = Command::new("cargo");
+        command.args(&cmd_args);
+
+        let records = match child_commands::run_command(
+            puzzle,
+            extra_bin.as_deref(),
+            command,
+            limits,
+            format,
+        ) {
+            Ok(records) => records.unwrap_or_default(),
+            Err(e) => {
+                eprintln!("✘ Failed to run the solution: {e:?}");
+                process::exit(1);
+            }
+        };
+
+        if format == OutputFormat::Json {
+            println!("{}", records_to_json(&records));
+        }
+
+        // a solution that reported nothing did not run, e.g. because its input is missing.
+        if records.is_empty() || records.iter().any(|record| record.status.is_failure()) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if !format.is_text() {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.to_string());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -30,5 +101,9 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    // e.g. a part that panicked, the solution reported it already.
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
@@ -1,40 +1,119 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process};
 
+use crate::template::compare::{compare, print_comparisons};
+use crate::template::history::{self, HistoryEntry};
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{
+    all_days, limits::Limits, readme_benchmarks, Day, OutputFormat, PuzzleId, Registry, Year,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+/// Bench a single day of `year` or, if `day` is `None`, every day of `year` that needs benching.
+/// `year` is `None` for the default year.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    registry: &Registry,
+    year: Option<Year>,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    is_isolated: bool,
+    limits: Limits,
+    format: OutputFormat,
+    compare_threshold: Option<f64>,
+) {
     let stored_timings = Timings::read_from_file();
 
+    let puzzles = all_days().map(|day| PuzzleId::new(year, day));
+
     let days_to_run = day.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                puzzles.collect()
+            } else if compare_threshold.is_some() {
+                // when comparing, re-bench every day that has stored timings.
+                puzzles
+                    .filter(|puzzle| stored_timings.get(*puzzle).is_some())
+                    .collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                puzzles
+                    .filter(|puzzle| !stored_timings.is_day_complete(*puzzle))
                     .collect()
             }
         },
-        |day| HashSet::from([day]),
+        |day| HashSet::from([PuzzleId::new(year, day)]),
+    );
+
+    let (summary, timings) = run_multi(
+        registry,
+        &days_to_run,
+        true,
+        true,
+        is_isolated,
+        1,
+        limits,
+        format,
     );
+    let timings = timings.unwrap();
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let mut regressions = 0;
+
+    if let Some(threshold) = compare_threshold {
+        let comparisons = compare(&stored_timings, &timings);
+        if format.is_text() {
+            regressions = print_comparisons(&comparisons, threshold);
+        } else {
+            regressions = comparisons
+                .iter()
+                .filter(|c| c.is_regression(threshold))
+                .count();
+        }
+    }
 
     if store {
+        // in-process runs are benched with the profile of the runner itself.
+        let profile = if is_isolated
+            || limits.is_set()
+            || !registry.is_in_process()
+            || !cfg!(debug_assertions)
+        {
+            "release"
+        } else {
+            "debug"
+        };
+
+        if let Err(e) = history::append(&HistoryEntry::new(timings.clone(), profile)) {
+            eprintln!("Failed to append benchmarks to history: {e}");
+        }
+
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
-        println!();
         match readme_benchmarks::update(merged_timings) {
             Ok(()) => {
-                println!("Stored updated benchmarks.");
+                if format.is_text() {
+                    println!();
+                    println!("Stored updated benchmarks.");
+                }
             }
             Err(_) => {
                 eprintln!("Failed to store updated benchmarks.");
             }
         }
     }
+
+    if regressions > 0 {
+        eprintln!("{regressions} part(s) regressed beyond the threshold.");
+        process::exit(1);
+    }
+
+    if summary.has_failures() {
+        process::exit(1);
+    }
+}
+
+/// Print how the timings of a puzzle developed over all stored runs.
+pub fn handle_history(puzzle: PuzzleId) {
+    history::print_day(&history::read_from_file(), puzzle);
 }
@@ -0,0 +1,42 @@
+use super::{check_answers, Check};
+use crate::template::{
+    all_days, has_answers, limits::Limits, Answers, Day, PuzzleId, Registry, Year,
+};
+
+/// Verify the solutions of `year` against the accepted answers recorded in `data/answers`.
+/// Verifies every day with recorded answers if `day` is `None`. Exits with a non-zero status if any answer is wrong.
+pub fn handle(
+    registry: &Registry,
+    year: Option<Year>,
+    day: Option<Day>,
+    is_release: bool,
+    is_isolated: bool,
+    limits: Limits,
+) {
+    let puzzles: Vec<PuzzleId> = match day {
+        Some(day) => vec![PuzzleId::new(year, day)],
+        None => all_days()
+            .map(|day| PuzzleId::new(year, day))
+            .filter(|puzzle| has_answers(*puzzle))
+            .collect(),
+    };
+
+    // variants and extra binaries have to give the accepted answer as well.
+    check_answers(
+        registry,
+        &puzzles,
+        is_release,
+        is_isolated,
+        limits,
+        ("Answers", "verified"),
+        |puzzle| {
+            let answers =
+                Answers::read(puzzle).map_err(|e| format!("Could not read answers: {e}"))?;
+
+            Ok(vec![Check {
+                example: None,
+                expected: [1, 2].map(|part| answers.get(part).map(String::from)),
+            }])
+        },
+    );
+}
@@ -0,0 +1,160 @@
+use std::{path::PathBuf, process::Command, thread, time::Duration};
+
+use crate::template::{
+    diff_answers, limits::Limits, run_multi::child_commands, watcher::FileWatcher, OutputFormat,
+    PartRecord, PuzzleId, ANSI_BOLD, ANSI_RESET,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// Editors often write a file in several steps, wait for them to finish before rebuilding.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Rebuild and re-run the tests and the real input of `puzzle` whenever its solution, `src/lib.rs`,
+/// its examples or its input change. Runs until interrupted.
+pub fn handle(puzzle: PuzzleId, bin: Option<String>, release: bool) {
+    let bin = bin.unwrap_or_else(|| puzzle.to_string());
+    let extra_bin = (bin != puzzle.to_string()).then_some(bin.as_str());
+
+    let mut watcher = FileWatcher::new(
+        [
+            format!("src/bin/{bin}.rs"),
+            "src/lib.rs".into(),
+            format!("data/examples/{puzzle}.txt"),
+            format!("data/examples/{puzzle}.json"),
+            format!("data/inputs/{puzzle}.txt"),
+        ]
+        .map(PathBuf::from),
+    );
+
+    let mut previous: Option<Vec<PartRecord>> = None;
+
+    loop {
+        if let Some(records) = run(puzzle, &bin, extra_bin, release) {
+            if let Some(previous) = &previous {
+                let changes = diff_answers(previous, &records);
+                if changes.is_empty() {
+                    println!("Answers unchanged.");
+                } else {
+                    println!("Changed answers:");
+                    for change in changes {
+                        println!("  {change}");
+                    }
+                }
+            }
+            previous = Some(records);
+        }
+
+        println!("---");
+        println!("👀 Watching day {puzzle} for changes, press Ctrl-C to stop.");
+
+        let changed = loop {
+            thread::sleep(POLL_INTERVAL);
+            let changed: Vec<String> = watcher
+                .changed()
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            if !changed.is_empty() {
+                thread::sleep(DEBOUNCE);
+                watcher.changed();
+                break changed;
+            }
+        };
+
+        println!();
+        println!("{ANSI_BOLD}Changed: {}{ANSI_RESET}", changed.join(", "));
+    }
+}
+
+/// Builds the solution and runs its tests and the real input. Returns `None` if the build failed.
+fn run(
+    puzzle: PuzzleId,
+    bin: &str,
+    extra_bin: Option<&str>,
+    release: bool,
+) -> Option<Vec<PartRecord>> {
+    let builds = match child_commands::build_bins(&[bin.into()], release) {
+        Ok(builds) => builds,
+        Err(e) => {
+            eprintln!("Failed to build the solution: {e:?}");
+            return None;
+        }
+    };
+
+    // compiler errors are shown as they are, the build is retried on the next change.
+    builds.print_errors();
+    let executable = builds.executable(bin)?;
+
+    run_tests(bin, release);
+
+    match child_commands::run_solution(
+        puzzle,
+        extra_bin,
+        None,
+        executable,
+        false,
+        Limits::default(),
+        OutputFormat::Text,
+    ) {
+        Ok(Some(records)) => Some(records),
+        Ok(None) => {
+            println!("No answers, is the input file empty?");
+            Some(vec![])
+        }
+        Err(e) => {
+            eprintln!("Failed to run the solution: {e:?}");
+            None
+        }
+    }
+}
+
+/// Runs the tests of the solution, printing a single line if they pass and the failures otherwise.
+fn run_tests(bin: &str, release: bool) {
+    let mut args = vec!["test", "--quiet", "--bin", bin];
+    if release {
+        args.push("--release");
+    }
+
+    // backtraces would bury the failed assertions.
+    let output = match Command::new("cargo")
+        .args(&args)
+        .env("RUST_BACKTRACE", "0")
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to run tests: {e}");
+            return;
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // e.g. `test result: ok. 2 passed; 0 failed; 0 ignored; ...`, shortened to `2 passed; 0 failed`.
+    let summary = stdout
+        .lines()
+        .rfind(|line| line.starts_with("test result:"))
+        .and_then(|line| line.split_once(". "))
+        .map(|(_, counts)| counts.split("; ").take(2).collect::<Vec<_>>().join("; "))
+        .unwrap_or_default();
+
+    if output.status.success() {
+        println!("Tests: ✔ {summary}");
+        return;
+    }
+
+    // the first `failures:` section holds the output of the failed tests, the second one just their names.
+    let failures: Vec<&str> = stdout
+        .lines()
+        .skip_while(|line| *line != "failures:")
+        .skip(1)
+        .take_while(|line| *line != "failures:")
+        .collect();
+
+    if failures.is_empty() {
+        // e.g. the tests did not compile.
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+    } else {
+        println!("{}", failures.join("\n").trim());
+    }
+    println!("Tests: ✘ {summary}");
+}
@@ -0,0 +1,207 @@
+/// Compares fresh benchmark results against stored timings.
+use crate::template::{
+    timings::{Timing, Timings},
+    PuzzleId, ANSI_BOLD, ANSI_RESET,
+};
+
+/// The change in duration of a single part between two benchmark runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartComparison {
+    pub puzzle: PuzzleId,
+    /// The extra binary or variant that was compared, see [`Timing::source`].
+    pub source: Option<String>,
+    pub part: u8,
+    pub stored_nanos: f64,
+    /// `None` if the part failed, e.g. because it timed out.
+    pub current_nanos: Option<f64>,
+}
+
+impl PartComparison {
+    /// Relative change in percent, positive if the part got slower. A failed part got infinitely slower.
+    pub fn change_percent(&self) -> f64 {
+        match self.current_nanos {
+            Some(current_nanos) => (current_nanos - self.stored_nanos) / self.stored_nanos * 100.0,
+            None => f64::INFINITY,
+        }
+    }
+
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.change_percent() > threshold_percent
+    }
+}
+
+/// Compares every part in `current` with its stored counterpart.
+/// Parts that are missing on either side or have no measurable stored duration are skipped,
+/// parts that failed in `current` are compared as regressions.
+pub fn compare(stored: &Timings, current: &Timings) -> Vec<PartComparison> {
+    current
+        .data
+        .iter()
+        .filter_map(|timing| Some((timing, stored.get_matching(timing)?)))
+        .flat_map(|(timing, stored_timing)| {
+            [1, 2]
+                .into_iter()
+                .filter_map(move |part| compare_part(stored_timing, timing, part))
+        })
+        .collect()
+}
+
+fn compare_part(stored: &Timing, current: &Timing, part: u8) -> Option<PartComparison> {
+    let stored_nanos = stored.part_nanos(part).filter(|x| *x > 0.0)?;
+    let current_nanos = match current.part_nanos(part) {
+        Some(current_nanos) => Some(current_nanos),
+        None if current.failed_parts.contains(&part) => None,
+        None => return None,
+    };
+
+    Some(PartComparison {
+        puzzle: current.puzzle(),
+        source: current.source(),
+        part,
+        stored_nanos,
+        current_nanos,
+    })
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", std::time::Duration::from_nanos(nanos as u64))
+}
+
+/// Print a comparison table and return the number of parts that regressed beyond `threshold_percent`.
+pub fn print_comparisons(comparisons: &[PartComparison], threshold_percent: f64) -> usize {
+    println!();
+    println!(
+        "{ANSI_BOLD}Comparison with stored timings{ANSI_RESET} (threshold: {threshold_percent}%)"
+    );
+    println!("------");
+
+    if comparisons.is_empty() {
+        println!("No stored timings to compare with.");
+    }
+
+    let mut regressions = 0;
+
+    for comparison in comparisons {
+        let is_regression = comparison.is_regression(threshold_percent);
+        if is_regression {
+            regressions += 1;
+        }
+
+        let source = comparison
+            .source
+            .as_ref()
+            .map(|source| format!(" ({source})"))
+            .unwrap_or_default();
+
+        let change = match comparison.current_nanos {
+            Some(current_nanos) => format!(
+                "{} ({:+.1}%)",
+                format_nanos(current_nanos),
+                comparison.change_percent()
+            ),
+            None => "failed".into(),
+        };
+
+        println!(
+            "Day {}{} Part {}: {} → {change}{}",
+            comparison.puzzle,
+            source,
+            comparison.part,
+            format_nanos(comparison.stored_nanos),
+            if is_regression { " ✘ regression" } else { "" }
+        );
+    }
+
+    regressions
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::compare;
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+        template::Day,
+    };
+
+    fn timing(day: Day, part_1: Option<&str>, part_2: Option<&str>) -> Timing {
+        Timing {
+            year: None,
+            day,
+            bin: None,
+            variant: None,
+            parse: None,
+            part_1: part_1.map(Into::into),
+            part_2: part_2.map(Into::into),
+            total_nanos: 0_f64,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            failed_parts: vec![],
+        }
+    }
+
+    #[test]
+    fn compares_matching_parts() {
+        let stored = Timings {
+            data: vec![timing(day!(1), Some("10.0ms"), Some("20.0ms"))],
+        };
+        let current = Timings {
+            data: vec![timing(day!(1), Some("30.0ms"), Some("19.0ms"))],
+        };
+
+        let comparisons = compare(&stored, &current);
+        assert_eq!(comparisons.len(), 2);
+        assert_eq!(comparisons[0].change_percent(), 200.0);
+        assert!(comparisons[0].is_regression(10.0));
+        assert_eq!(comparisons[1].change_percent(), -5.0);
+        assert!(!comparisons[1].is_regression(10.0));
+    }
+
+    #[test]
+    fn respects_threshold() {
+        let stored = Timings {
+            data: vec![timing(day!(1), Some("10.0ms"), None)],
+        };
+        let current = Timings {
+            data: vec![timing(day!(1), Some("11.5ms"), None)],
+        };
+
+        let comparisons = compare(&stored, &current);
+        assert!(comparisons[0].is_regression(10.0));
+        assert!(!comparisons[0].is_regression(20.0));
+    }
+
+    #[test]
+    fn skips_parts_without_stored_timings() {
+        let stored = Timings {
+            data: vec![timing(day!(1), None, Some("1.0ms"))],
+        };
+        let current = Timings {
+            data: vec![
+                timing(day!(1), Some("1.0ms"), Some("1.0ms")),
+                timing(day!(2), Some("1.0ms"), Some("1.0ms")),
+            ],
+        };
+
+        let comparisons = compare(&stored, &current);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].part, 2);
+    }
+
+    #[test]
+    fn reports_failed_parts_as_regressions() {
+        let stored = Timings {
+            data: vec![timing(day!(1), Some("1.0ms"), Some("1.0ms"))],
+        };
+        let mut failed = timing(day!(1), Some("1.0ms"), None);
+        failed.failed_parts = vec![2];
+        let current = Timings { data: vec![failed] };
+
+        let comparisons = compare(&stored, &current);
+        assert_eq!(comparisons.len(), 2);
+        assert_eq!(comparisons[1].current_nanos, None);
+        assert!(comparisons[1].is_regression(1000.0));
+    }
+}
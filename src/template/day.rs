@@ -25,9 +25,9 @@ impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        // if day == 0 || day > 25 {
-        //     return None;
-        // }
+        if day == 0 || day > 25 {
+            return None;
+        }
         Some(Self(day))
     }
 
@@ -157,6 +157,17 @@ macro_rules! day {
 mod tests {
     use super::{all_days, Day};
 
+    #[test]
+    fn validates_days() {
+        assert_eq!(Day::new(1), Some(Day(1)));
+        assert_eq!(Day::new(25), Some(Day(25)));
+        assert_eq!(Day::new(0), None);
+        assert_eq!(Day::new(26), None);
+        assert_eq!("05".parse::<Day>().unwrap(), Day(5));
+        assert!("26".parse::<Day>().is_err());
+        assert!("99".parse::<Day>().is_err());
+    }
+
     #[test]
     fn all_days_iterator() {
         let mut iter = all_days();
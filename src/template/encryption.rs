@@ -0,0 +1,190 @@
+/// At-rest encryption of inputs and puzzle descriptions, so they can be committed without redistributing them.
+/// Encrypted files live next to the plain files with an `.enc` suffix, e.g. `data/inputs/01.txt.enc`.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Nonce,
+};
+use sha2::{Digest, Sha256};
+
+static KEY_FILE_PATH: &str = ".aoc-input-key";
+static MAGIC: &[u8] = b"AOCENC1\n";
+const NONCE_LEN: usize = 12;
+
+pub const ENCRYPTED_SUFFIX: &str = "enc";
+
+#[derive(Debug)]
+pub enum EncryptionError {
+    /// Neither `AOC_INPUT_KEY` nor the key file are set.
+    KeyNotFound,
+    /// The file is not encrypted, was encrypted with another key or was modified.
+    Decrypt(PathBuf),
+    Io(io::Error),
+}
+
+impl Display for EncryptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncryptionError::KeyNotFound => write!(
+                f,
+                "no encryption key found. Set `AOC_INPUT_KEY` or create the file `{KEY_FILE_PATH}`."
+            ),
+            EncryptionError::Decrypt(path) => write!(
+                f,
+                "could not decrypt \"{}\", is the encryption key correct?",
+                path.display()
+            ),
+            EncryptionError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for EncryptionError {
+    fn from(e: io::Error) -> Self {
+        EncryptionError::Io(e)
+    }
+}
+
+impl From<EncryptionError> for io::Error {
+    fn from(e: EncryptionError) -> Self {
+        match e {
+            EncryptionError::Io(e) => e,
+            e => io::Error::new(io::ErrorKind::InvalidData, e.to_string()),
+        }
+    }
+}
+
+/// A 256-bit key, derived from a passphrase of any length.
+pub struct Key([u8; 32]);
+
+impl Key {
+    pub fn from_passphrase(passphrase: &str) -> Self {
+        Self(Sha256::digest(passphrase.trim().as_bytes()).into())
+    }
+
+    /// Reads the passphrase from `AOC_INPUT_KEY`, falling back to the file `.aoc-input-key`.
+    pub fn from_env() -> Result<Self, EncryptionError> {
+        env::var("AOC_INPUT_KEY")
+            .ok()
+            .or_else(|| fs::read_to_string(KEY_FILE_PATH).ok())
+            .filter(|x| !x.trim().is_empty())
+            .map(|x| Self::from_passphrase(&x))
+            .ok_or(EncryptionError::KeyNotFound)
+    }
+
+    /// Encrypts `plaintext` with AES-256-GCM.
+    /// The nonce is derived from the key and the plaintext, so encrypting a file again does not change it in git.
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(self.0);
+        hasher.update(plaintext);
+        let nonce = &hasher.finalize()[..NONCE_LEN];
+
+        let ciphertext = self
+            .cipher()
+            .encrypt(Nonce::from_slice(nonce), plaintext)
+            .expect("encryption of in-memory data can't fail");
+
+        [MAGIC, nonce, &ciphertext].concat()
+    }
+
+    /// Decrypts data created by [`Key::encrypt`], returning `None` if it was encrypted with another key or was modified.
+    pub fn decrypt(&self, data: &[u8]) -> Option<Vec<u8>> {
+        let data = data.strip_prefix(MAGIC)?;
+        if data.len() < NONCE_LEN {
+            return None;
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        self.cipher()
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .ok()
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new(&self.0.into())
+    }
+}
+
+/// The path of the encrypted counterpart of `path`, e.g. `data/inputs/01.txt.enc`.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(ENCRYPTED_SUFFIX);
+    PathBuf::from(path)
+}
+
+/// Reads a text file, falling back to decrypting its encrypted counterpart if the plain file is missing or empty.
+/// Empty plain files are skipped, as `scaffold` creates them for inputs that were not downloaded yet.
+pub fn read_to_string(path: impl AsRef<Path>) -> io::Result<String> {
+    let path = path.as_ref();
+
+    match fs::read_to_string(path) {
+        Ok(contents) if !contents.trim().is_empty() => Ok(contents),
+        plain => {
+            let encrypted = encrypted_path(path);
+            if !encrypted.exists() {
+                return plain;
+            }
+            Ok(decrypt_file(&Key::from_env()?, &encrypted)?)
+        }
+    }
+}
+
+/// Decrypts the file at `path` to a string.
+pub fn decrypt_file(key: &Key, path: &Path) -> Result<String, EncryptionError> {
+    key.decrypt(&fs::read(path)?)
+        .and_then(|plaintext| String::from_utf8(plaintext).ok())
+        .ok_or_else(|| EncryptionError::Decrypt(path.to_path_buf()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs};
+
+    use super::{decrypt_file, encrypted_path, Key};
+
+    #[test]
+    fn encrypts_and_decrypts() {
+        let key = Key::from_passphrase("secret\n");
+        let encrypted = key.encrypt(b"1 2\n3 4\n");
+
+        assert!(!encrypted.windows(3).any(|x| x == b"1 2"));
+        assert_eq!(key.decrypt(&encrypted).unwrap(), b"1 2\n3 4\n");
+        // encrypting the same input again is stable.
+        assert_eq!(key.encrypt(b"1 2\n3 4\n"), encrypted);
+    }
+
+    #[test]
+    fn refuses_wrong_keys_and_modified_data() {
+        let encrypted = Key::from_passphrase("secret").encrypt(b"1 2\n");
+        assert!(Key::from_passphrase("other").decrypt(&encrypted).is_none());
+
+        let mut modified = encrypted.clone();
+        *modified.last_mut().unwrap() ^= 1;
+        assert!(Key::from_passphrase("secret").decrypt(&modified).is_none());
+        assert!(Key::from_passphrase("secret").decrypt(b"1 2\n").is_none());
+    }
+
+    #[test]
+    fn decrypts_files() {
+        let path = env::temp_dir().join(format!("aoc-encryption-{}.txt", std::process::id()));
+        let key = Key::from_passphrase("secret");
+        fs::write(encrypted_path(&path), key.encrypt(b"1 2\n")).unwrap();
+
+        assert_eq!(
+            encrypted_path(&path).extension().unwrap().to_str(),
+            Some("enc")
+        );
+        assert_eq!(decrypt_file(&key, &encrypted_path(&path)).unwrap(), "1 2\n");
+
+        fs::remove_file(encrypted_path(&path)).unwrap();
+    }
+}
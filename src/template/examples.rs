@@ -0,0 +1,331 @@
+/// Example inputs of a puzzle together with their expected answers.
+use std::{collections::HashMap, fmt::Display, fs, path::Path, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{normalize_input, try_read_file, PuzzleId, PuzzleParams};
+
+/// A single example input of a puzzle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Example {
+    /// Name of the example, used when reporting results.
+    pub name: String,
+    pub input: String,
+    /// The expected answers of part 1 and part 2, if known.
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    /// Parameters that differ between this example and the real input, sorted by name.
+    pub params: Vec<(String, String)>,
+}
+
+impl Example {
+    /// The expected answer of `part`, if known.
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+fn get_manifest_path(puzzle: PuzzleId) -> String {
+    format!("./data/examples/{puzzle}.json")
+}
+
+/// Whether `puzzle` has an examples manifest, i.e. a `data/examples/01.json` file.
+pub fn has_examples_manifest(puzzle: PuzzleId) -> bool {
+    Path::new(&get_manifest_path(puzzle)).exists()
+}
+
+/// Reads the examples of a puzzle from its manifest, e.g. `data/examples/01.json`.
+/// Without a manifest, the example file `data/examples/01.txt` is returned without expected answers.
+pub fn read_examples(puzzle: impl Into<PuzzleId>) -> Result<Vec<Example>, String> {
+    let puzzle = puzzle.into();
+
+    match fs::read_to_string(get_manifest_path(puzzle)) {
+        Ok(manifest) => parse_manifest(&manifest, puzzle),
+        Err(_) => {
+            let input = try_read_file("examples", puzzle).map_err(|e| e.to_string())?;
+            Ok(vec![Example {
+                name: format!("{puzzle}.txt"),
+                input,
+                part_1: None,
+                part_2: None,
+                params: vec![],
+            }])
+        }
+    }
+}
+
+/// Extracts the example of a puzzle from its description, as written to `data/puzzles` by `download`.
+/// The first code block is taken as the example input and the last emphasized code of each part, e.g. *`11`*,
+/// as its answer. The answers are a best guess, puzzles sometimes emphasize other values after the answer.
+pub fn extract_example(puzzle: PuzzleId, description: &str) -> Option<Example> {
+    // part two starts with the second heading, if it is unlocked.
+    let title_end = description.find("## ").map_or(0, |i| i + 3);
+    let (part_1, part_2) = match description[title_end..].find("\n## ") {
+        Some(i) => description.split_at(title_end + i),
+        None => (description, ""),
+    };
+
+    let start = part_1.find("```\n")? + 4;
+    let len = part_1[start..].find("```")?;
+    let input = normalize_input(&part_1[start..start + len]);
+    if input.is_empty() {
+        return None;
+    }
+
+    Some(Example {
+        name: format!("{puzzle}.txt"),
+        input,
+        part_1: last_emphasized_code(part_1),
+        part_2: last_emphasized_code(part_2),
+        params: vec![],
+    })
+}
+
+fn last_emphasized_code(text: &str) -> Option<String> {
+    let start = text.rfind("*`")? + 2;
+    let len = text[start..].find("`*")?;
+    Some(text[start..start + len].to_string())
+}
+
+/// Writes a manifest for `example`, which references the example file `data/examples/01.txt`.
+pub fn write_examples_manifest(puzzle: PuzzleId, example: &Example) -> Result<(), String> {
+    let mut entry = format!("{{ \"file\": \"{puzzle}.txt\"");
+    for (key, answer) in [("part_1", &example.part_1), ("part_2", &example.part_2)] {
+        if let Some(answer) = answer {
+            entry.push_str(&format!(", \"{key}\": {}", answer_to_json(answer)?));
+        }
+    }
+
+    fs::write(get_manifest_path(puzzle), format!("[{entry} }}]\n")).map_err(|e| e.to_string())
+}
+
+/// Answers are written as numbers where that is lossless, JSON numbers are read as `f64`.
+fn answer_to_json(answer: &str) -> Result<String, String> {
+    const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+    match answer.parse::<i64>() {
+        Ok(x) if x.abs() <= MAX_SAFE_INTEGER => Ok(x.to_string()),
+        _ => JsonValue::String(answer.into())
+            .stringify()
+            .map_err(|e| e.to_string()),
+    }
+}
+
+/// Test helper that runs `solve` against every example of `puzzle` with a known answer for `part`.
+/// Panics with the name of the example on the first wrong answer, or if no example has an answer for `part`.
+///
+/// ```ignore
+/// #[test]
+/// fn test_part_one() {
+///     advent_of_code::template::assert_examples(DAY, 1, part_one);
+/// }
+/// ```
+pub fn assert_examples<T: Display>(
+    puzzle: impl Into<PuzzleId>,
+    part: u8,
+    solve: impl Fn(&str) -> Option<T>,
+) {
+    assert_examples_with_params(puzzle, part, |input, _: &()| solve(input))
+}
+
+/// Like [`assert_examples`], for solutions declaring `params`.
+/// `solve` receives the parameters of each example, i.e. the defaults with the overrides of the manifest applied.
+///
+/// ```ignore
+/// #[test]
+/// fn test_part_one() {
+///     advent_of_code::template::assert_examples_with_params(DAY, 1, part_one);
+/// }
+/// ```
+pub fn assert_examples_with_params<P: PuzzleParams, T: Display>(
+    puzzle: impl Into<PuzzleId>,
+    part: u8,
+    solve: impl Fn(&str, &P) -> Option<T>,
+) {
+    let puzzle = puzzle.into();
+    let examples = read_examples(puzzle).expect("could not read examples");
+    let mut checked = 0;
+
+    for example in &examples {
+        let Some(expected) = example.expected(part) else {
+            continue;
+        };
+
+        let params = P::from_overrides(&example.params)
+            .unwrap_or_else(|e| panic!("invalid parameters of example `{}`: {e}", example.name));
+        let answer = solve(&example.input, &params).map(|x| x.to_string());
+        assert_eq!(
+            answer.as_deref(),
+            Some(expected),
+            "wrong answer for part {part} of example `{}`",
+            example.name
+        );
+        checked += 1;
+    }
+
+    // a test without a known answer would pass without checking anything.
+    assert!(
+        checked > 0,
+        "no example of day {puzzle} has an answer for part {part}"
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses a manifest, which is a JSON array of examples:
+///
+/// ```json
+/// [
+///   { "file": "01.txt", "part_1": "11", "part_2": "31" },
+///   { "name": "small", "input": "1 2\n", "part_1": 3, "params": { "rows": 7 } }
+/// ]
+/// ```
+///
+/// An example either references a `file` in `data/examples` or holds its `input` inline.
+fn parse_manifest(manifest: &str, puzzle: PuzzleId) -> Result<Vec<Example>, String> {
+    let json = JsonValue::from_str(manifest).or(Err("not valid JSON."))?;
+
+    json.get::<Vec<JsonValue>>()
+        .ok_or("Expected examples manifest to be an array.")?
+        .iter()
+        .enumerate()
+        .map(|(i, value)| parse_example(value, i, puzzle))
+        .collect()
+}
+
+fn parse_example(value: &JsonValue, index: usize, puzzle: PuzzleId) -> Result<Example, String> {
+    let json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected example to be a JSON object.")?;
+
+    let string = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+
+    let file = string("file");
+
+    let input = match (&file, string("input")) {
+        (_, Some(input)) => normalize_input(&input),
+        (Some(file), None) => fs::read_to_string(format!("./data/examples/{file}"))
+            .map(|raw| normalize_input(&raw))
+            .map_err(|e| format!("Could not read example file `{file}`: {e}"))?,
+        (None, None) => return Err("Expected example to have a `file` or an `input`.".into()),
+    };
+
+    let name = string("name")
+        .or(file)
+        .unwrap_or_else(|| format!("{puzzle} #{}", index + 1));
+
+    let params = match json.get("params") {
+        None => vec![],
+        Some(params) => {
+            let mut params = params
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected example.params to be an object.")?
+                .iter()
+                .map(|(key, value)| {
+                    answer_from_json(Some(value))
+                        .map(|value| (key.clone(), value))
+                        .ok_or(format!(
+                            "Expected example.params.{key} to be a string or number."
+                        ))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            params.sort();
+            params
+        }
+    };
+
+    Ok(Example {
+        name,
+        input,
+        part_1: answer_from_json(json.get("part_1")),
+        part_2: answer_from_json(json.get("part_2")),
+        params,
+    })
+}
+
+/// Reads an answer or parameter, which may be written as a string or a number.
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn answer_from_json(value: Option<&JsonValue>) -> Option<String> {
+    match value? {
+        JsonValue::String(x) => Some(x.clone()),
+        JsonValue::Number(x) if x.fract() == 0.0 => Some(format!("{}", *x as i64)),
+        JsonValue::Number(x) => Some(x.to_string()),
+        _ => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answer_to_json, assert_examples, extract_example, parse_manifest};
+    use crate::day;
+
+    #[test]
+    fn parses_inline_examples() {
+        let manifest = r#"[
+            { "input": "1 2\r\n", "part_1": 3, "part_2": "a,b" },
+            { "name": "small", "input": "4", "params": { "size": 7, "rows": "3" } }
+        ]"#;
+        let examples = parse_manifest(manifest, day!(1).into()).unwrap();
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].name, "01 #1");
+        assert_eq!(examples[0].input, "1 2");
+        assert_eq!(examples[0].expected(1), Some("3"));
+        assert_eq!(examples[0].expected(2), Some("a,b"));
+        assert_eq!(examples[1].name, "small");
+        assert_eq!(examples[1].expected(1), None);
+        assert_eq!(
+            examples[1].params,
+            [("rows".into(), "3".into()), ("size".into(), "7".into())]
+        );
+    }
+
+    #[test]
+    fn errors_for_malformed_manifests() {
+        assert!(parse_manifest("{}", day!(1).into()).is_err());
+        assert!(parse_manifest(r#"[{ "part_1": 1 }]"#, day!(1).into()).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "no example of day 04 has an answer for part 1")]
+    fn fails_without_known_answers() {
+        // day 4 has an example file, but no manifest with its answers.
+        assert_examples(day!(4), 1, |_| Some(0));
+    }
+
+    #[test]
+    fn extracts_examples_from_descriptions() {
+        let description = "## --- Day 1: Historian Hysteria ---\n\n\
+            For example:\n\n```\n3   4\n4   3\n```\n\n\
+            Pair up *`3`* and `4`. The total distance is *`11`*.\n\n\
+            ## --- Part Two ---\n\n\
+            So, for these example lists, the similarity score is *`31`*.\n";
+
+        let example = extract_example(day!(1).into(), description).unwrap();
+        assert_eq!(example.name, "01.txt");
+        assert_eq!(example.input, "3   4\n4   3");
+        assert_eq!(example.expected(1), Some("11"));
+        assert_eq!(example.expected(2), Some("31"));
+
+        let part_one = description.split("## --- Part Two").next().unwrap();
+        let example = extract_example(day!(1).into(), part_one).unwrap();
+        assert_eq!(example.expected(1), Some("11"));
+        assert_eq!(example.expected(2), None);
+
+        assert!(extract_example(day!(1).into(), "## --- Day 1 ---\n\nNo code.\n").is_none());
+    }
+
+    #[test]
+    fn writes_answers_losslessly() {
+        assert_eq!(answer_to_json("-31").unwrap(), "-31");
+        assert_eq!(
+            answer_to_json("9007199254740993").unwrap(),
+            "\"9007199254740993\""
+        );
+        assert_eq!(answer_to_json("6,1").unwrap(), "\"6,1\"");
+    }
+}
@@ -0,0 +1,315 @@
+/// Append-only log of benchmark runs, stored next to the latest timings.
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{Error, Write},
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{timings::Timings, PuzzleId, ANSI_BOLD, ANSI_RESET};
+
+static HISTORY_FILE_PATH: &str = "./data/timings_history.jsonl";
+
+/// A single benchmark run in the history log.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Short git revision of the working tree, suffixed with `-dirty` if it had uncommitted changes.
+    pub revision: Option<String>,
+    /// Cargo profile the solutions were benched with.
+    pub profile: String,
+    /// Label of the machine the benchmarks ran on.
+    pub machine: Option<String>,
+    pub timings: Timings,
+}
+
+impl HistoryEntry {
+    /// Creates an entry for a run that just finished on the current machine.
+    pub fn new(timings: Timings, profile: &str) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        Self {
+            timestamp,
+            revision: get_revision(),
+            profile: profile.into(),
+            machine: get_machine(),
+            timings,
+        }
+    }
+}
+
+/// Appends an entry to the history log as a single line of JSON.
+pub fn append(entry: &HistoryEntry) -> Result<(), Error> {
+    let json = JsonValue::from(entry).stringify().map_err(Error::other)?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_FILE_PATH)?;
+    writeln!(file, "{json}")
+}
+
+/// Reads all entries of the history log. Lines that can't be parsed are skipped.
+pub fn read_from_file() -> Vec<HistoryEntry> {
+    fs::read_to_string(HISTORY_FILE_PATH)
+        .map(|s| parse_lines(&s))
+        .unwrap_or_default()
+}
+
+fn parse_lines(s: &str) -> Vec<HistoryEntry> {
+    s.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| HistoryEntry::try_from(line).ok())
+        .collect()
+}
+
+/// Label of the current machine, taken from `AOC_MACHINE` and falling back to the host name.
+fn get_machine() -> Option<String> {
+    ["AOC_MACHINE", "HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|key| env::var(key).ok().filter(|x| !x.is_empty()))
+}
+
+fn get_revision() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    let mut revision = String::from_utf8(output.stdout).ok()?.trim().to_string();
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain"])
+        .output()
+        .is_ok_and(|output| !output.stdout.is_empty());
+
+    if is_dirty {
+        revision.push_str("-dirty");
+    }
+
+    Some(revision)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Formats a unix timestamp as UTC date and time, e.g. `2024-12-01 05:00`.
+fn format_timestamp(timestamp: u64) -> String {
+    let (days, secs) = (timestamp / 86_400, timestamp % 86_400);
+
+    // civil date from days since epoch, see: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60
+    )
+}
+
+/// Formats the change of a part compared to the previous run that benched it.
+fn format_trend(previous: Option<f64>, current: f64) -> String {
+    match previous {
+        Some(previous) if previous > 0.0 => {
+            format!(" ({:+.1}%)", (current - previous) / previous * 100.0)
+        }
+        _ => String::new(),
+    }
+}
+
+/// Prints the timings of a puzzle for every run in the history, oldest first.
+pub fn print_day(entries: &[HistoryEntry], puzzle: PuzzleId) {
+    println!("{ANSI_BOLD}History of day {puzzle}{ANSI_RESET}");
+    println!("------");
+
+    let mut previous: [Option<f64>; 2] = [None, None];
+    let mut has_entries = false;
+
+    for entry in entries {
+        let Some(timing) = entry.timings.get(puzzle) else {
+            continue;
+        };
+
+        has_entries = true;
+
+        let parts: Vec<String> = [1, 2]
+            .iter()
+            .map(|&part| {
+                let index = usize::from(part - 1);
+                let duration_str = if part == 1 {
+                    &timing.part_1
+                } else {
+                    &timing.part_2
+                };
+
+                match (duration_str, timing.part_nanos(part)) {
+                    (Some(duration_str), Some(nanos)) => {
+                        let trend = format_trend(previous[index], nanos);
+                        previous[index] = Some(nanos);
+                        format!("Part {part}: {duration_str}{trend}")
+                    }
+                    _ => format!("Part {part}: -"),
+                }
+            })
+            .collect();
+
+        println!(
+            "{} | {} | {} | {} | {}",
+            format_timestamp(entry.timestamp),
+            entry.revision.as_deref().unwrap_or("-"),
+            entry.profile,
+            entry.machine.as_deref().unwrap_or("-"),
+            parts.join(" | ")
+        );
+    }
+
+    if !has_entries {
+        println!("No stored benchmarks.");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn optional_string(value: Option<&String>) -> JsonValue {
+    value.map_or(JsonValue::Null, |x| JsonValue::String(x.clone()))
+}
+
+impl From<&HistoryEntry> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &HistoryEntry) -> Self {
+        let mut json = JsonValue::from(value.timings.clone());
+
+        if let JsonValue::Object(map) = &mut json {
+            map.insert(
+                "timestamp".into(),
+                JsonValue::Number(value.timestamp as f64),
+            );
+            map.insert("revision".into(), optional_string(value.revision.as_ref()));
+            map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+            map.insert("machine".into(), optional_string(value.machine.as_ref()));
+        }
+
+        json
+    }
+}
+
+impl TryFrom<&str> for HistoryEntry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+
+        let map = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let timestamp = map
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let string = |key: &str| map.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        Ok(HistoryEntry {
+            timestamp,
+            revision: string("revision"),
+            profile: string("profile").ok_or("Expected entry.profile to be a string.")?,
+            machine: string("machine"),
+            timings: Timings::try_from(&json)?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{format_timestamp, format_trend, parse_lines, HistoryEntry};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    fn get_mock_entry(timestamp: u64, part_1: &str) -> HistoryEntry {
+        HistoryEntry {
+            timestamp,
+            revision: Some("abc1234".into()),
+            profile: "release".into(),
+            machine: None,
+            timings: Timings {
+                data: vec![Timing {
+                    year: None,
+                    day: day!(1),
+                    bin: None,
+                    variant: None,
+                    parse: None,
+                    part_1: Some(part_1.into()),
+                    part_2: None,
+                    total_nanos: 0_f64,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    failed_parts: vec![],
+                }],
+            },
+        }
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let entry = get_mock_entry(1_733_029_200, "1.0ms");
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        let parsed = HistoryEntry::try_from(line.as_str()).unwrap();
+        assert_eq!(parsed.timestamp, 1_733_029_200);
+        assert_eq!(parsed.revision, Some("abc1234".into()));
+        assert_eq!(parsed.profile, "release");
+        assert_eq!(parsed.machine, None);
+        assert_eq!(parsed.timings.data[0].part_1, Some("1.0ms".into()));
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        let first = JsonValue::from(&get_mock_entry(1, "1.0ms"))
+            .stringify()
+            .unwrap();
+        let second = JsonValue::from(&get_mock_entry(2, "2.0ms"))
+            .stringify()
+            .unwrap();
+        let entries = parse_lines(&format!("{first}\n{{ broken\n\n{second}\n"));
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].timestamp, 2);
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_029_200), "2024-12-01 05:00");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+    }
+
+    #[test]
+    fn formats_trends() {
+        assert_eq!(format_trend(None, 10.0), "");
+        assert_eq!(format_trend(Some(10.0), 15.0), " (+50.0%)");
+        assert_eq!(format_trend(Some(10.0), 5.0), " (-50.0%)");
+    }
+}
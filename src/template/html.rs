@@ -0,0 +1,228 @@
+/// Minimal conversion of puzzle pages to markdown. Only handles the handful of tags used in puzzle descriptions.
+/// Converts the articles of a page, i.e. the puzzle descriptions or the message of a submission, to markdown.
+pub fn articles_to_markdown(html: &str) -> String {
+    let articles: Vec<String> = extract_articles(html)
+        .into_iter()
+        .map(|article| html_to_markdown(article).trim_end().to_string())
+        .collect();
+
+    articles.join("\n\n") + "\n"
+}
+
+/// Returns the contents of every `<article>` element of a page.
+fn extract_articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let content_start = start + open_end + 1;
+        let Some(len) = rest[content_start..].find("</article>") else {
+            break;
+        };
+        articles.push(&rest[content_start..content_start + len]);
+        rest = &rest[content_start + len..];
+    }
+
+    articles
+}
+
+/// Converts a fragment of html to markdown.
+pub fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut in_code = false;
+    // answers are emphasized code, e.g. `<code><em>11</em></code>`, which is written as *`11`*.
+    let mut is_emphasized_code = false;
+    let mut links: Vec<String> = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut out, rest, in_pre);
+            break;
+        };
+
+        push_text(&mut out, &rest[..start], in_pre);
+
+        let Some(len) = rest[start..].find('>') else {
+            push_text(&mut out, &rest[start..], in_pre);
+            break;
+        };
+
+        let tag = &rest[start + 1..start + len];
+        rest = &rest[start + len + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match (name.as_str(), is_closing) {
+            ("h1" | "h2" | "h3", false) => {
+                start_block(&mut out);
+                out.push_str("## ");
+            }
+            ("h1" | "h2" | "h3" | "p", true) => end_block(&mut out),
+            ("p", false) => start_block(&mut out),
+            ("pre", false) => {
+                start_block(&mut out);
+                out.push_str("```\n");
+                in_pre = true;
+            }
+            ("pre", true) => {
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```");
+                end_block(&mut out);
+                in_pre = false;
+            }
+            ("code", false) if !in_pre => {
+                out.push('`');
+                in_code = true;
+            }
+            ("code", true) if !in_pre => {
+                out.push('`');
+                if is_emphasized_code {
+                    out.push('*');
+                }
+                in_code = false;
+                is_emphasized_code = false;
+            }
+            ("em", false) if in_code && out.ends_with('`') => {
+                out.pop();
+                out.push_str("*`");
+                is_emphasized_code = true;
+            }
+            ("em", _) if !in_pre && !in_code => out.push('*'),
+            ("ul", false) => start_block(&mut out),
+            ("ul", true) => end_block(&mut out),
+            ("li", false) => {
+                trim_spaces(&mut out);
+                if !out.is_empty() && !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("- ");
+            }
+            ("li", true) => trim_spaces(&mut out),
+            ("a", false) => {
+                links.push(get_attribute(tag, "href").unwrap_or_default());
+                out.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            ("br", _) => out.push('\n'),
+            _ => {}
+        }
+    }
+
+    out.trim().to_string() + "\n"
+}
+
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    let text = decode_entities(text);
+
+    if in_pre {
+        out.push_str(&text);
+        return;
+    }
+
+    // outside of `<pre>`, whitespace is insignificant and collapsed into single spaces.
+    let mut is_space = out.is_empty() || out.ends_with([' ', '\n']);
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !is_space {
+                out.push(' ');
+            }
+            is_space = true;
+        } else {
+            out.push(c);
+            is_space = false;
+        }
+    }
+}
+
+fn trim_spaces(out: &mut String) {
+    out.truncate(out.trim_end_matches(' ').len());
+}
+
+fn start_block(out: &mut String) {
+    trim_spaces(out);
+    if out.is_empty() {
+        return;
+    }
+    while !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
+
+fn end_block(out: &mut String) {
+    trim_spaces(out);
+    out.push_str("\n\n");
+}
+
+fn get_attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let len = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + len]))
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{articles_to_markdown, html_to_markdown};
+
+    #[test]
+    fn converts_puzzle_descriptions() {
+        let html = r#"<h2>--- Day 1: Historian Hysteria ---</h2><p>The <em>Chief Historian</em> is
+            always <a href="/2024/day/1">present</a>.</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<ul><li>Pair up <code>1</code> and <code><em>3</em></code>.</li><li>x &lt; y &amp;&amp; y &gt; z</li></ul>
+<p>Done.</p>"#;
+
+        assert_eq!(
+            html_to_markdown(html),
+            "## --- Day 1: Historian Hysteria ---\n\n\
+             The *Chief Historian* is always [present](/2024/day/1).\n\n\
+             ```\n3   4\n4   3\n```\n\n\
+             - Pair up `1` and *`3`*.\n\
+             - x < y && y > z\n\n\
+             Done.\n"
+        );
+    }
+
+    #[test]
+    fn extracts_articles() {
+        let html = r#"<html><main>
+<article class="day-desc"><h2>Part one</h2><p>a</p></article>
+<p>Your puzzle answer was <code>1</code>.</p>
+<article class="day-desc"><h2 id="part2">Part two</h2><p>b</p></article>
+</main></html>"#;
+
+        assert_eq!(
+            articles_to_markdown(html),
+            "## Part one\n\na\n\n## Part two\n\nb\n"
+        );
+    }
+}
@@ -0,0 +1,60 @@
+/// Brings an input into its canonical form:
+///  1. a leading byte order mark is stripped.
+///  2. line endings are normalized to `\n`.
+///  3. trailing whitespace at the end of the input, including the final newline, is stripped.
+///
+/// Whitespace within and at the end of other lines is kept, as some puzzles rely on it.
+pub fn normalize_input(raw: &str) -> String {
+    let input = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+    let input = input.replace("\r\n", "\n").replace('\r', "\n");
+    input.trim_end().to_string()
+}
+
+/// Splits a normalized input into its sections, which are separated by blank lines.
+/// Leading and trailing newlines of a section are trimmed and empty sections are skipped.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .split("\n\n")
+        .map(|section| section.trim_matches('\n'))
+        .filter(|section| !section.is_empty())
+}
+
+/// Splits a normalized input at its first blank line, for puzzles made up of exactly two sections.
+pub fn split_sections(input: &str) -> Option<(&str, &str)> {
+    let (first, second) = input.split_once("\n\n")?;
+    Some((first.trim_matches('\n'), second.trim_matches('\n')))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{normalize_input, sections, split_sections};
+
+    #[test]
+    fn normalizes_line_endings() {
+        assert_eq!(normalize_input("a\r\nb\r\n"), "a\nb");
+        assert_eq!(normalize_input("a\rb"), "a\nb");
+        assert_eq!(normalize_input("a\nb"), "a\nb");
+    }
+
+    #[test]
+    fn normalizes_trailing_whitespace() {
+        assert_eq!(normalize_input("a \nb  \n\n\n"), "a \nb");
+        assert_eq!(normalize_input("  a\n"), "  a");
+        assert_eq!(normalize_input("\n \n"), "");
+    }
+
+    #[test]
+    fn strips_byte_order_marks() {
+        assert_eq!(normalize_input("\u{feff}1 2\r\n"), "1 2");
+    }
+
+    #[test]
+    fn splits_sections() {
+        let input = normalize_input("a\nb\r\n\r\nc\n\n\nd\n");
+        assert_eq!(sections(&input).collect::<Vec<_>>(), ["a\nb", "c", "d"]);
+        assert_eq!(split_sections(&input), Some(("a\nb", "c\n\n\nd")));
+        assert_eq!(split_sections("a\nb"), None);
+    }
+}
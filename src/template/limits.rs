@@ -0,0 +1,381 @@
+/// Wall-clock and memory limits for solutions running as child processes.
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    fs, iter,
+    process::{Child, Command, Stdio},
+    time::{Duration, Instant},
+};
+
+/// Reading the memory usage of the process tree is more expensive than checking the clock.
+const MEMORY_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Limits of a single solution run. Both are unlimited by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    pub timeout: Option<Duration>,
+    /// Ceiling of the resident memory in bytes, only enforced on Linux.
+    pub memory: Option<u64>,
+}
+
+impl Limits {
+    /// Reads the limits from `AOC_TIMEOUT` and `AOC_MEMORY_LIMIT`, e.g. to set a default timeout in `.cargo/config.toml`.
+    pub fn from_env() -> Result<Self, String> {
+        Ok(Self {
+            timeout: env_var("AOC_TIMEOUT")
+                .map(|x| parse_timeout(&x))
+                .transpose()?,
+            memory: env_var("AOC_MEMORY_LIMIT")
+                .map(|x| parse_memory_limit(&x))
+                .transpose()?,
+        })
+    }
+
+    /// Fills limits that are not set with those of `defaults`.
+    #[must_use]
+    pub fn or(self, defaults: Self) -> Self {
+        Self {
+            timeout: self.timeout.or(defaults.timeout),
+            memory: self.memory.or(defaults.memory),
+        }
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.timeout.is_some() || self.memory.is_some()
+    }
+}
+
+fn env_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|x| !x.trim().is_empty())
+}
+
+/// Parses a timeout like `10`, `2.5s`, `500ms` or `1m`. Plain numbers are seconds.
+pub fn parse_timeout(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let (number, unit) = split_unit(value);
+
+    let seconds_per_unit = match unit {
+        "" | "s" => 1.0,
+        "ms" => 0.001,
+        "m" => 60.0,
+        _ => {
+            return Err(format!(
+                "unknown unit in timeout `{value}`, use `ms`, `s` or `m`"
+            ))
+        }
+    };
+
+    number
+        .parse::<f64>()
+        .ok()
+        .filter(|x| x.is_finite() && *x > 0.0)
+        .map(|x| Duration::from_secs_f64(x * seconds_per_unit))
+        .ok_or_else(|| format!("invalid timeout `{value}`"))
+}
+
+/// Parses a memory limit like `512`, `512M` or `2G`. Plain numbers are MiB.
+pub fn parse_memory_limit(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let (number, unit) = split_unit(value);
+
+    let shift = match unit
+        .to_ascii_uppercase()
+        .trim_end_matches("IB")
+        .trim_end_matches('B')
+    {
+        "K" => 10,
+        "" | "M" => 20,
+        "G" => 30,
+        _ => {
+            return Err(format!(
+                "unknown unit in memory limit `{value}`, use `K`, `M` or `G`"
+            ))
+        }
+    };
+
+    number
+        .parse::<u64>()
+        .ok()
+        .filter(|x| *x > 0)
+        .and_then(|x| x.checked_mul(1 << shift))
+        .ok_or_else(|| format!("invalid memory limit `{value}`"))
+}
+
+fn split_unit(value: &str) -> (&str, &str) {
+    let i = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    value.split_at(i)
+}
+
+/// Formats a number of bytes in the largest binary unit that fits, e.g. `512 MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1 << 30 && b % (1 << 30) == 0 => format!("{} GiB", b >> 30),
+        b if b >= 1 << 20 => format!("{} MiB", b >> 20),
+        b => format!("{} KiB", b >> 10),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A limit that a run exceeded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitExceeded {
+    Timeout(Duration),
+    Memory(u64),
+}
+
+impl Display for LimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LimitExceeded::Timeout(timeout) => write!(f, "timed out after {timeout:?}"),
+            LimitExceeded::Memory(memory) => {
+                write!(f, "exceeded the memory limit of {}", format_bytes(*memory))
+            }
+        }
+    }
+}
+
+/// Enforces [`Limits`] on a child process, killing it together with its own children once a limit is exceeded.
+/// The child is either the compiled solution or `cargo run`, which runs the solution as a child process of its own.
+pub struct Watchdog {
+    limits: Limits,
+    started: Instant,
+    memory_checked: Instant,
+}
+
+impl Watchdog {
+    /// Starts the clock. Build the solution before, compile times don't count towards the timeout.
+    pub fn new(limits: Limits) -> Self {
+        if limits.memory.is_some() && !cfg!(target_os = "linux") {
+            eprintln!("Warning: memory limits are only supported on Linux.");
+        }
+
+        Self {
+            limits,
+            started: Instant::now(),
+            memory_checked: Instant::now(),
+        }
+    }
+
+    /// Checks the limits, killing the child if one was exceeded. Children that exited already are left alone.
+    pub fn check(&mut self, child: &mut Child) -> Option<LimitExceeded> {
+        if !matches!(child.try_wait(), Ok(None)) {
+            return None;
+        }
+
+        let exceeded = self.exceeded(child.id());
+        if exceeded.is_some() {
+            kill_tree(child);
+        }
+        exceeded
+    }
+
+    fn exceeded(&mut self, pid: u32) -> Option<LimitExceeded> {
+        if let Some(timeout) = self.limits.timeout {
+            if self.started.elapsed() > timeout {
+                return Some(LimitExceeded::Timeout(timeout));
+            }
+        }
+
+        if let Some(memory) = self.limits.memory {
+            if self.memory_checked.elapsed() >= MEMORY_POLL_INTERVAL {
+                self.memory_checked = Instant::now();
+                if get_tree_rss(pid).is_some_and(|rss| rss > memory) {
+                    return Some(LimitExceeded::Memory(memory));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Kills a process and all of its descendants. Killing just `cargo run` would leave the solution running.
+fn kill_tree(child: &mut Child) {
+    let pid = child.id().to_string();
+
+    let command = if cfg!(windows) {
+        let mut command = Command::new("taskkill");
+        command.args(["/F", "/T", "/PID", &pid]);
+        Some(command)
+    } else {
+        match get_descendants(child.id()) {
+            // the whole tree is killed at once, parents first, so a solution can't react to its killed children
+            // by reporting answers. Orphaned children were collected before and are killed all the same.
+            Some(descendants) => {
+                let mut command = Command::new("kill");
+                command
+                    .args(["-KILL", &pid])
+                    .args(descendants.iter().map(u32::to_string));
+                Some(command)
+            }
+            // without `/proc`, only the direct children can be found.
+            None => {
+                let mut command = Command::new("pkill");
+                command.args(["-KILL", "-P", &pid]);
+                Some(command)
+            }
+        }
+    };
+
+    if let Some(mut command) = command {
+        let status = command.stdout(Stdio::null()).stderr(Stdio::null()).status();
+        if let Err(e) = status {
+            eprintln!("Could not kill the children of process {pid}: {e}");
+        }
+    }
+
+    let _ = child.kill();
+}
+
+/// The resident memory of a process and its descendants in bytes, read from `/proc`. `None` on other platforms.
+fn get_tree_rss(root: u32) -> Option<u64> {
+    let total = iter::once(root)
+        .chain(get_descendants(root)?)
+        .filter_map(|pid| fs::read_to_string(format!("/proc/{pid}/status")).ok())
+        .filter_map(|status| parse_rss(&status))
+        .sum();
+
+    Some(total)
+}
+
+/// The descendants of a process read from `/proc`, every parent before its children. `None` on other platforms.
+fn get_descendants(root: u32) -> Option<Vec<u32>> {
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+
+    for entry in fs::read_dir("/proc").ok()?.filter_map(Result::ok) {
+        let Some(pid) = entry.file_name().to_str().and_then(|x| x.parse().ok()) else {
+            continue;
+        };
+        if let Some(parent) = fs::read_to_string(entry.path().join("stat"))
+            .ok()
+            .and_then(|stat| parse_parent_pid(&stat))
+        {
+            children.entry(parent).or_default().push(pid);
+        }
+    }
+
+    let mut descendants = vec![];
+    let mut pending: Vec<u32> = children.get(&root).cloned().unwrap_or_default();
+
+    while let Some(pid) = pending.pop() {
+        descendants.push(pid);
+        pending.extend(children.get(&pid).into_iter().flatten());
+    }
+
+    Some(descendants)
+}
+
+/// The parent pid in `/proc/<pid>/stat`, e.g. `42 (cargo) S 41 ...`. The name may contain spaces and parentheses.
+fn parse_parent_pid(stat: &str) -> Option<u32> {
+    let rest = &stat[stat.rfind(')')? + 1..];
+    rest.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// The resident memory in bytes in `/proc/<pid>/status`, e.g. `VmRSS:     1234 kB`.
+fn parse_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        fs,
+        process::{Command, Stdio},
+        thread,
+        time::Duration,
+    };
+
+    use super::{
+        format_bytes, get_descendants, kill_tree, parse_memory_limit, parse_parent_pid, parse_rss,
+        parse_timeout, LimitExceeded,
+    };
+
+    #[test]
+    fn parses_timeouts() {
+        assert_eq!(parse_timeout("10").unwrap(), Duration::from_secs(10));
+        assert_eq!(parse_timeout("2.5s").unwrap(), Duration::from_millis(2500));
+        assert_eq!(parse_timeout("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse_timeout("1m").unwrap(), Duration::from_secs(60));
+        assert!(parse_timeout("0").is_err());
+        assert!(parse_timeout("10h").is_err());
+        assert!(parse_timeout("soon").is_err());
+    }
+
+    #[test]
+    fn parses_memory_limits() {
+        assert_eq!(parse_memory_limit("512").unwrap(), 512 << 20);
+        assert_eq!(parse_memory_limit("512M").unwrap(), 512 << 20);
+        assert_eq!(parse_memory_limit("2GiB").unwrap(), 2 << 30);
+        assert_eq!(parse_memory_limit("64kb").unwrap(), 64 << 10);
+        assert!(parse_memory_limit("1T").is_err());
+        assert!(parse_memory_limit("0").is_err());
+        assert_eq!(format_bytes(2 << 30), "2 GiB");
+        assert_eq!(format_bytes(1536 << 20), "1536 MiB");
+    }
+
+    #[test]
+    fn describes_exceeded_limits() {
+        assert_eq!(
+            LimitExceeded::Timeout(Duration::from_secs(10)).to_string(),
+            "timed out after 10s"
+        );
+        assert_eq!(
+            LimitExceeded::Memory(512 << 20).to_string(),
+            "exceeded the memory limit of 512 MiB"
+        );
+    }
+
+    #[test]
+    fn parses_proc_files() {
+        assert_eq!(
+            parse_parent_pid("42 (my (odd) name) S 41 42 42 0"),
+            Some(41)
+        );
+        assert_eq!(
+            parse_rss("Name:\tcargo\nVmPeak:\t  9000 kB\nVmRSS:\t  1234 kB\n"),
+            Some(1234 * 1024)
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn kills_grandchildren() {
+        // a shell running a shell running `sleep`, like `cargo run` running a solution that spawns processes.
+        let mut child = Command::new("sh")
+            .args(["-c", "sh -c 'sleep 60; true' & sleep 60; true"])
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let mut descendants = vec![];
+        for _ in 0..100 {
+            descendants = get_descendants(child.id()).unwrap();
+            if descendants.len() == 3 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(descendants.len(), 3);
+
+        kill_tree(&mut child);
+        child.wait().unwrap();
+        thread::sleep(Duration::from_millis(100));
+
+        // killed processes without a parent to reap them may linger as zombies.
+        let is_running = |pid: u32| {
+            fs::read_to_string(format!("/proc/{pid}/stat")).is_ok_and(|stat| {
+                stat.rsplit_once(") ")
+                    .is_some_and(|(_, x)| !x.starts_with('Z'))
+            })
+        };
+        assert!(!descendants.into_iter().any(is_running));
+    }
+}
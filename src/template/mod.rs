@@ -1,68 +1,359 @@
-use std::{env, fs};
+use std::{env, io};
 
 pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod limits;
 pub mod runner;
+pub mod templates;
 
+pub use answers::*;
 pub use day::*;
+pub use examples::*;
+pub use input::*;
+pub use params::*;
+pub use puzzle::*;
+pub use registry::*;
+pub use report::*;
+pub use stats::*;
+pub use year::*;
 
+mod answers;
+mod compare;
 mod day;
+mod encryption;
+mod examples;
+mod history;
+mod html;
+mod input;
+mod params;
+mod puzzle;
 mod readme_benchmarks;
+mod registry;
+mod report;
 mod run_multi;
+mod stats;
+mod submissions;
 mod timings;
+mod watcher;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// Accepts a [`Day`] of the default year or a [`PuzzleId`], e.g. `01.txt` or `2023-01.txt`.
+/// The contents are normalized with [`normalize_input`].
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file(folder: &str, puzzle: impl Into<PuzzleId>) -> String {
+    try_read_file(folder, puzzle).expect("could not open input file")
+}
+
+/// Helper function that reads a text file to a string, returning an error if it can't be read.
+pub fn try_read_file(folder: &str, puzzle: impl Into<PuzzleId>) -> io::Result<String> {
+    let cwd = env::current_dir()?;
+    let filepath = cwd
+        .join("data")
+        .join(folder)
+        .join(format!("{}.txt", puzzle.into()));
+    encryption::read_to_string(filepath).map(|raw| normalize_input(&raw))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+/// The contents are normalized with [`normalize_input`].
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: impl Into<PuzzleId>, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
         .join("data")
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+        .join(format!("{}-{part}.txt", puzzle.into()));
+    let f = encryption::read_to_string(filepath);
+    normalize_input(&f.expect("could not open input file"))
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `PUZZLE` and sets up the input and runner for each part.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The macro accepts these optional arguments after the day:
+/// - `1` or `2` to only run a single part of the solution.
+/// - `year = 2023` for solutions of a year other than the default year.
+/// - `variants = { fast: 1 => part_one_fast }` to declare named, alternate implementations of a part,
+///   which can be run with `--variant <name>` or `--all-variants`.
+/// - `parse = parse` to parse the input once, timed separately from the parts. Parts and variants then
+///   receive a reference to the parsed input instead of the input string. The parsed input has to be owned data.
+/// - `params = { rows: i32 = 103, cols: i32 = 101 }` to declare parameters that differ between the examples
+///   and the real input. This generates a `Params` struct holding the real-input defaults, which parts and
+///   variants receive as their second argument. Examples override them in their manifest, runs with `--param rows=7`.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    (@args [$day:expr] [$year:expr] [$($parse:tt)*] $params:tt [$($parts:tt)*] [$($variants:tt)*] 1 $(, $($rest:tt)*)?) => {
+        $crate::solution!(@args [$day] [$year] [$($parse)*] $params [[part_one, 1]] [$($variants)*] $($($rest)*)?);
+    };
+    (@args [$day:expr] [$year:expr] [$($parse:tt)*] $params:tt [$($parts:tt)*] [$($variants:tt)*] 2 $(, $($rest:tt)*)?) => {
+        $crate::solution!(@args [$day] [$year] [$($parse)*] $params [[part_two, 2]] [$($variants)*] $($($rest)*)?);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    (@args [$day:expr] [$year:expr] [$($parse:tt)*] $params:tt [$($parts:tt)*] [$($variants:tt)*] year = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@args [$day] [Some($crate::year!($value))] [$($parse)*] $params [$($parts)*] [$($variants)*] $($($rest)*)?);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    (@args [$day:expr] [$year:expr] [$($parse:tt)*] $params:tt [$($parts:tt)*] [$($variants:tt)*] parse = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@args [$day] [$year] [$value] $params [$($parts)*] [$($variants)*] $($($rest)*)?);
     };
+    (
+        @args [$day:expr] [$year:expr] [$($parse:tt)*] $params:tt [$($parts:tt)*] [$($variants:tt)*]
+        params = { $( $name:ident : $ty:ty = $default:expr ),* $(,)? } $(, $($rest:tt)*)?
+    ) => {
+        $crate::solution!(@args [$day] [$year] [$($parse)*] [$( [$name, $ty, $default] )*] [$($parts)*] [$($variants)*] $($($rest)*)?);
+    };
+    (
+        @args [$day:expr] [$year:expr] [$($parse:tt)*] $params:tt [$($parts:tt)*] [$($variants:tt)*]
+        variants = { $( $name:ident : $part:literal => $func:expr ),* $(,)? } $(, $($rest:tt)*)?
+    ) => {
+        $crate::solution!(@args [$day] [$year] [$($parse)*] $params [$($parts)*] [$($variants)* $( [$name, $part, $func] )*] $($($rest)*)?);
+    };
+    (@args [$day:expr] [$year:expr] [] $params:tt [$($parts:tt)*] [$($variants:tt)*]) => {
+        $crate::solution!(@impl $day, $year, $params, [$($parts)*] [$($variants)*]);
+    };
+    (@args [$day:expr] [$year:expr] [$parse:expr] $params:tt [$($parts:tt)*] [$($variants:tt)*]) => {
+        $crate::solution!(@impl_parsed $day, $year, $parse, $params, [$($parts)*] [$($variants)*]);
+    };
+
+    (@params []) => {};
+    (@params [$( [$name:ident, $ty:ty, $default:expr] )+]) => {
+        /// Parameters that differ between the examples and the real input.
+        #[derive(Clone, Debug)]
+        pub struct Params {
+            $( pub $name: $ty, )+
+        }
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        impl Default for Params {
+            /// The parameters of the real input.
+            fn default() -> Self {
+                Self {
+                    $( $name: $default, )+
+                }
+            }
+        }
+
+        impl $crate::template::PuzzleParams for Params {
+            fn from_overrides(overrides: &[(String, String)]) -> Result<Self, String> {
+                let mut params = Self::default();
+                for (name, value) in overrides {
+                    $(
+                        if name == stringify!($name) {
+                            params.$name = $crate::template::parse_param_value(name, value)?;
+                            continue;
+                        }
+                    )+
+                    return Err(format!("unknown parameter `{name}`"));
+                }
+                Ok(params)
+            }
+        }
+    };
+
+    (@params_type []) => { () };
+    (@params_type [$($spec:tt)+]) => { Params };
+
+    (@call $func:expr, $input:expr, $params:ident, []) => { $func($input) };
+    (@call $func:expr, $input:expr, $params:ident, [$($spec:tt)+]) => { $func($input, &$params) };
+
+    (@common $day:expr, $year:expr, $params:tt) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The current puzzle, used to locate its input and examples.
+        const PUZZLE: $crate::template::PuzzleId = $crate::template::PuzzleId::new($year, DAY);
+
+        $crate::solution!(@params $params);
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        // Not part of the public API
+        #[doc(hidden)]
+        pub struct __Solution;
+
+        /// Entry point for the in-process solution registry.
+        pub static SOLUTION: &dyn $crate::template::Solution = &__Solution;
+    };
+
+    (
+        @impl $day:expr, $year:expr, $params:tt,
+        [$( [$func:expr, $part:expr] )*]
+        [$( [$variant:ident, $variant_part:expr, $variant_func:expr] )*]
+    ) => {
+        $crate::solution!(@common $day, $year, $params);
+
+        impl $crate::template::Solution for __Solution {
+            fn day(&self) -> $crate::template::Day {
+                DAY
+            }
+
+            fn year(&self) -> Option<$crate::template::Year> {
+                PUZZLE.year
+            }
+
+            fn parts(&self) -> &'static [u8] {
+                &[$( $part ),*]
+            }
+
+            fn params(&self, overrides: &[(String, String)]) -> Result<Box<dyn std::any::Any>, String> {
+                <$crate::solution!(@params_type $params) as $crate::template::PuzzleParams>::from_overrides(overrides)
+                    .map(|params| Box::new(params) as Box<dyn std::any::Any>)
+            }
+
+            fn solve(
+                &self,
+                variant: Option<&str>,
+                part: u8,
+                input: &str,
+                params: &dyn std::any::Any,
+            ) -> Option<String> {
+                #[allow(unused_variables)]
+                let params = $crate::template::__downcast_params::<$crate::solution!(@params_type $params)>(params);
+                match variant {
+                    None => {
+                        $(
+                            if part == $part {
+                                return $crate::solution!(@call $func, input, params, $params).map(|answer| answer.to_string());
+                            }
+                        )*
+                    }
+                    Some(variant) => {
+                        $(
+                            if variant == stringify!($variant) && part == $variant_part {
+                                return $crate::solution!(@call $variant_func, input, params, $params).map(|answer| answer.to_string());
+                            }
+                        )*
+                    }
+                }
+                None
+            }
+
+            fn variants(&self) -> &'static [(&'static str, u8)] {
+                &[$( (stringify!($variant), $variant_part) ),*]
+            }
+        }
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            #[allow(unused_variables)]
+            let (input, params): (String, $crate::solution!(@params_type $params)) = input_from_args(PUZZLE);
+            let variants = Variants::from_args(SOLUTION);
+            let mut records = vec![];
+            if variants.runs_default() {
+                $( records.push(run_part(|input| $crate::solution!(@call $func, input, params, $params), &input, PUZZLE, $part)); )*
+            }
+            $(
+                if variants.runs(stringify!($variant)) {
+                    records.push(run_variant_part(|input| $crate::solution!(@call $variant_func, input, params, $params), &input, PUZZLE, $variant_part, stringify!($variant)));
+                }
+            )*
+            print_disagreements(&records);
+            print_records(&records);
+            exit_on_failure(&records);
         }
     };
+
+    (
+        @impl_parsed $day:expr, $year:expr, $parse:expr, $params:tt,
+        [$( [$func:expr, $part:expr] )*]
+        [$( [$variant:ident, $variant_part:expr, $variant_func:expr] )*]
+    ) => {
+        $crate::solution!(@common $day, $year, $params);
+
+        impl $crate::template::Solution for __Solution {
+            fn day(&self) -> $crate::template::Day {
+                DAY
+            }
+
+            fn year(&self) -> Option<$crate::template::Year> {
+                PUZZLE.year
+            }
+
+            fn parts(&self) -> &'static [u8] {
+                &[$( $part ),*]
+            }
+
+            fn params(&self, overrides: &[(String, String)]) -> Result<Box<dyn std::any::Any>, String> {
+                <$crate::solution!(@params_type $params) as $crate::template::PuzzleParams>::from_overrides(overrides)
+                    .map(|params| Box::new(params) as Box<dyn std::any::Any>)
+            }
+
+            fn solve(
+                &self,
+                variant: Option<&str>,
+                part: u8,
+                input: &str,
+                params: &dyn std::any::Any,
+            ) -> Option<String> {
+                self.solve_parsed(variant, part, &$parse(input), params)
+            }
+
+            fn variants(&self) -> &'static [(&'static str, u8)] {
+                &[$( (stringify!($variant), $variant_part) ),*]
+            }
+
+            fn parser(&self) -> Option<$crate::template::Parser> {
+                Some(|input| Box::new($parse(input)))
+            }
+
+            fn solve_parsed(
+                &self,
+                variant: Option<&str>,
+                part: u8,
+                parsed: &dyn std::any::Any,
+                params: &dyn std::any::Any,
+            ) -> Option<String> {
+                let parsed = $crate::template::__downcast_parsed(parsed, $parse);
+                #[allow(unused_variables)]
+                let params = $crate::template::__downcast_params::<$crate::solution!(@params_type $params)>(params);
+                match variant {
+                    None => {
+                        $(
+                            if part == $part {
+                                return $crate::solution!(@call $func, parsed, params, $params).map(|answer| answer.to_string());
+                            }
+                        )*
+                    }
+                    Some(variant) => {
+                        $(
+                            if variant == stringify!($variant) && part == $variant_part {
+                                return $crate::solution!(@call $variant_func, parsed, params, $params).map(|answer| answer.to_string());
+                            }
+                        )*
+                    }
+                }
+                None
+            }
+        }
+
+        fn main() {
+            use $crate::template::runner::*;
+            #[allow(unused_variables)]
+            let (input, params): (String, $crate::solution!(@params_type $params)) = input_from_args(PUZZLE);
+            let variants = Variants::from_args(SOLUTION);
+            let (parsed, record) = run_parse($parse, &input, PUZZLE);
+            let mut records = vec![record];
+            if let Some(parsed) = &parsed {
+                if variants.runs_default() {
+                    $( records.push(run_part(|parsed| $crate::solution!(@call $func, parsed, params, $params), parsed, PUZZLE, $part)); )*
+                }
+                $(
+                    if variants.runs(stringify!($variant)) {
+                        records.push(run_variant_part(|parsed| $crate::solution!(@call $variant_func, parsed, params, $params), parsed, PUZZLE, $variant_part, stringify!($variant)));
+                    }
+                )*
+            }
+            print_disagreements(&records);
+            print_records(&records);
+            exit_on_failure(&records);
+        }
+    };
+
+    ($day:expr $(, $($args:tt)*)?) => {
+        $crate::solution!(@args [$day] [None] [] [] [[part_one, 1] [part_two, 2]] [] $($($args)*)?);
+    };
 }
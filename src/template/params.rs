@@ -0,0 +1,68 @@
+/// Parameters of a puzzle that differ between its examples and the real input, e.g. the size of a grid.
+/// Implemented by the `Params` struct that [`solution!`](crate::solution) generates for `params = { .. }`.
+pub trait PuzzleParams: Sized {
+    /// Applies overrides of the form `(name, value)` to the defaults of the real input.
+    fn from_overrides(overrides: &[(String, String)]) -> Result<Self, String>;
+}
+
+/// Solutions without parameters don't accept any overrides.
+impl PuzzleParams for () {
+    fn from_overrides(overrides: &[(String, String)]) -> Result<Self, String> {
+        match overrides.first() {
+            Some((name, _)) => Err(format!("unknown parameter `{name}`")),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parses a parameter override of the form `name=value`, e.g. `rows=7`.
+pub fn parse_param_override(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!(
+            "expecting a parameter in the form of `name=value`, got `{s}`"
+        )),
+    }
+}
+
+/// Parses the value of a parameter override, used by the `Params` struct generated by [`solution!`](crate::solution).
+pub fn parse_param_value<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value `{value}` for parameter `{name}`"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_param_override, parse_param_value, PuzzleParams};
+
+    #[test]
+    fn parses_overrides() {
+        assert_eq!(
+            parse_param_override("rows=7").unwrap(),
+            ("rows".into(), "7".into())
+        );
+        assert_eq!(
+            parse_param_override(" size = 12 ").unwrap(),
+            ("size".into(), "12".into())
+        );
+        assert!(parse_param_override("rows").is_err());
+        assert!(parse_param_override("=7").is_err());
+    }
+
+    #[test]
+    fn parses_values() {
+        assert_eq!(parse_param_value::<i32>("rows", "7"), Ok(7));
+        assert!(parse_param_value::<i32>("rows", "seven").is_err());
+    }
+
+    #[test]
+    fn rejects_overrides_without_params() {
+        assert!(<()>::from_overrides(&[]).is_ok());
+        assert!(<()>::from_overrides(&[("rows".into(), "7".into())]).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::{Day, Year};
+
+/// Identifies a puzzle by its day and, for puzzles outside of the repository's default year, its year.
+///
+/// Puzzles of the default year (`AOC_YEAR`) keep the single-year layout, i.e. `src/bin/05.rs`,
+/// while puzzles of other years are prefixed with their year, i.e. `src/bin/2023-05.rs`.
+///
+/// # Display
+/// This value displays as the file stem used for the puzzle's binary and data files.
+///
+/// ```
+/// # use advent_of_code::{day, year, template::PuzzleId};
+/// assert_eq!(PuzzleId::new(None, day!(5)).to_string(), "05");
+/// assert_eq!(PuzzleId::new(Some(year!(2023)), day!(5)).to_string(), "2023-05");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    /// The year of the puzzle, `None` for the default year.
+    pub year: Option<Year>,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Option<Year>, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Creates a [`PuzzleId`], dropping the year if it is the repository's default year.
+    pub fn resolve(year: Option<Year>, day: Day) -> Self {
+        Self::new(year.filter(|year| Some(*year) != Year::from_env()), day)
+    }
+
+    /// The year of the puzzle, falling back to the default year.
+    pub fn year_or_default(&self) -> Option<Year> {
+        self.year.or_else(Year::from_env)
+    }
+
+    /// When the puzzle unlocks in seconds since the unix epoch, i.e. at midnight EST (05:00 UTC) on its day.
+    /// `None` if the puzzle has no year and the default year is not set.
+    pub fn unlocks_at(&self) -> Option<u64> {
+        let year = u64::from(self.year_or_default()?.into_inner());
+
+        // days since epoch of december 1st, see: https://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let era = year / 400;
+        let yoe = year - era * 400;
+        let doy = (153 * 9 + 2) / 5;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        let days = era * 146_097 + doe - 719_468 + u64::from(self.day.into_inner()) - 1;
+
+        Some(days * 86_400 + 5 * 3600)
+    }
+}
+
+impl From<Day> for PuzzleId {
+    fn from(day: Day) -> Self {
+        Self::new(None, day)
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.year {
+            Some(year) => write!(f, "{year}-{}", self.day),
+            None => write!(f, "{}", self.day),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for PuzzleId {
+    type Err = PuzzleIdFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('-') {
+            Some((year, day)) => Ok(Self::new(
+                Some(year.parse().map_err(|_| PuzzleIdFromStrError)?),
+                day.parse().map_err(|_| PuzzleIdFromStrError)?,
+            )),
+            None => Ok(Self::from(
+                s.parse::<Day>().map_err(|_| PuzzleIdFromStrError)?,
+            )),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`PuzzleId`].
+#[derive(Debug)]
+pub struct PuzzleIdFromStrError;
+
+impl Error for PuzzleIdFromStrError {}
+
+impl Display for PuzzleIdFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a puzzle in the form of `05` or `2023-05`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PuzzleId;
+    use crate::{day, year};
+
+    #[test]
+    fn displays_file_stems() {
+        assert_eq!(PuzzleId::from(day!(5)).to_string(), "05");
+        assert_eq!(
+            PuzzleId::new(Some(year!(2023)), day!(25)).to_string(),
+            "2023-25"
+        );
+    }
+
+    #[test]
+    fn roundtrips_file_stems() {
+        for puzzle in [
+            PuzzleId::from(day!(1)),
+            PuzzleId::new(Some(year!(2015)), day!(12)),
+        ] {
+            assert_eq!(puzzle.to_string().parse::<PuzzleId>().unwrap(), puzzle);
+        }
+        assert!("2014-01".parse::<PuzzleId>().is_err());
+        assert!("2023-".parse::<PuzzleId>().is_err());
+    }
+
+    #[test]
+    fn sorts_default_year_first() {
+        let mut puzzles = vec![
+            PuzzleId::new(Some(year!(2023)), day!(1)),
+            PuzzleId::from(day!(2)),
+            PuzzleId::new(Some(year!(2016)), day!(3)),
+            PuzzleId::from(day!(1)),
+        ];
+        puzzles.sort();
+        let stems: Vec<String> = puzzles.iter().map(ToString::to_string).collect();
+        assert_eq!(stems, ["01", "02", "2016-03", "2023-01"]);
+    }
+
+    #[test]
+    fn unlocks_at_midnight_est() {
+        assert_eq!(
+            PuzzleId::new(Some(year!(2024)), day!(1)).unlocks_at(),
+            Some(1_733_029_200)
+        );
+        assert_eq!(
+            PuzzleId::new(Some(year!(2015)), day!(25)).unlocks_at(),
+            Some(1_451_019_600)
+        );
+    }
+}
@@ -3,7 +3,6 @@
 use std::{fs, io};
 
 use crate::template::timings::Timings;
-use crate::template::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -26,8 +25,8 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(bin: &str) -> String {
+    format!("./src/bin/{bin}.rs")
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
@@ -55,20 +54,47 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![MARKER.into(), header];
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+    // the parse column is only shown once a solution declares a parse step.
+    let has_parse = timings.data.iter().any(|timing| timing.parse.is_some());
+
+    // timings are sorted with the default year first, every other year gets a table of its own.
+    let mut current_year = None;
+
+    for (i, timing) in timings.data.into_iter().enumerate() {
+        if i == 0 || timing.year != current_year {
+            if let Some(year) = timing.year {
+                lines.push(String::new());
+                lines.push(format!("{prefix}# {year}"));
+            }
+            lines.push(String::new());
+            if has_parse {
+                lines.push("| Day | Parse | Part 1 | Part 2 |".into());
+                lines.push("| :---: | :---: | :---: | :---:  |".into());
+            } else {
+                lines.push("| Day | Part 1 | Part 2 |".into());
+                lines.push("| :---: | :---: | :---:  |".into());
+            }
+            current_year = timing.year;
+        }
+
+        let path = get_path_for_bin(&timing.bin_name());
+        // extra binaries and variants are listed next to the day they solve.
+        let label = match timing.source() {
+            Some(source) => format!("Day {} ({source})", timing.day.into_inner()),
+            None => format!("Day {}", timing.day.into_inner()),
+        };
+        let parse = if has_parse {
+            format!(" `{}` |", timing.parse.unwrap_or_else(|| "-".into()))
+        } else {
+            String::new()
+        };
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
+            "| [{}]({}) |{} `{}` | `{}` |",
+            label,
             path,
+            parse,
             timing.part_1.unwrap_or_else(|| "-".into()),
             timing.part_2.unwrap_or_else(|| "-".into())
         ));
@@ -100,28 +126,52 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{day, template::timings::Timing, template::timings::Timings, year};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
+                    year: None,
                     day: day!(1),
+                    bin: None,
+                    variant: None,
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    failed_parts: vec![],
                 },
                 Timing {
+                    year: None,
                     day: day!(2),
+                    bin: None,
+                    variant: None,
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    failed_parts: vec![],
                 },
                 Timing {
+                    year: None,
                     day: day!(4),
+                    bin: None,
+                    variant: None,
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    failed_parts: vec![],
                 },
             ],
         }
@@ -180,4 +230,68 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_of_extra_binaries() {
+        let mut timings = get_mock_timings();
+        timings.data[1].day = day!(1);
+        timings.data[1].bin = Some("26".into());
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 1 (26)](./src/bin/26.rs) | `30ms` | `40ms` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_of_variants() {
+        let mut timings = get_mock_timings();
+        timings.data[1].day = day!(1);
+        timings.data[1].variant = Some("fast".into());
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| [Day 1 (fast)](./src/bin/01.rs) | `30ms` | `40ms` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_of_other_years() {
+        let mut timings = get_mock_timings();
+        timings.data[2].year = Some(year!(2023));
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
+            "",
+            "### 2023",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 4](./src/bin/2023-04.rs) | `40ms` | `50ms` |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_of_parse_steps() {
+        let mut timings = get_mock_timings();
+        timings.data[0].parse = Some("5ms".into());
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `5ms` | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `-` | `30ms` | `40ms` |"));
+    }
 }
@@ -0,0 +1,334 @@
+/// In-process access to the solutions in `src/bin`.
+use std::{any::Any, fs, path::Path};
+
+use crate::template::{Day, PuzzleId, PuzzleParams, Year};
+
+/// The type-erased parse step of a solution, see [`Solution::parser`].
+pub type Parser = fn(&str) -> Box<dyn Any>;
+
+/// A solution that can be called in-process.
+/// Implemented for every day by the [`solution!`](crate::solution) macro.
+pub trait Solution: Sync {
+    /// The day this solution solves.
+    fn day(&self) -> Day;
+
+    /// The year of the puzzle, `None` for the default year.
+    fn year(&self) -> Option<Year> {
+        None
+    }
+
+    /// The puzzle this solution solves.
+    fn puzzle(&self) -> PuzzleId {
+        PuzzleId::new(self.year(), self.day())
+    }
+
+    /// The parts this solution implements.
+    fn parts(&self) -> &'static [u8];
+
+    /// The type-erased parameters of this solution, i.e. its defaults with `overrides` applied,
+    /// see [`PuzzleParams`]. They are parsed once and passed to every call of [`Solution::solve`].
+    fn params(&self, overrides: &[(String, String)]) -> Result<Box<dyn Any>, String> {
+        <()>::from_overrides(overrides).map(|params| Box::new(params) as Box<dyn Any>)
+    }
+
+    /// Runs a part, or a variant of it, against `input`, returning its answer in display form.
+    /// `params` are the output of [`Solution::params`].
+    fn solve(
+        &self,
+        variant: Option<&str>,
+        part: u8,
+        input: &str,
+        params: &dyn Any,
+    ) -> Option<String>;
+
+    /// The named, alternate implementations of parts as `(variant, part)` pairs.
+    fn variants(&self) -> &'static [(&'static str, u8)] {
+        &[]
+    }
+
+    /// The shared parse step of this solution, if it declares one.
+    /// Its output is passed to [`Solution::solve_parsed`] for every part.
+    fn parser(&self) -> Option<Parser> {
+        None
+    }
+
+    /// Runs a part, or a variant of it, against the output of [`Solution::parser`].
+    fn solve_parsed(
+        &self,
+        _variant: Option<&str>,
+        _part: u8,
+        _parsed: &dyn Any,
+        _params: &dyn Any,
+    ) -> Option<String> {
+        None
+    }
+}
+
+// Not part of the public API
+#[doc(hidden)]
+pub fn __downcast_parsed<T: 'static>(parsed: &dyn Any, _parse: fn(&str) -> T) -> &T {
+    parsed
+        .downcast_ref()
+        .expect("parsed input is passed to the solution that parsed it")
+}
+
+// Not part of the public API
+#[doc(hidden)]
+pub fn __downcast_params<P: PuzzleParams + 'static>(params: &dyn Any) -> &P {
+    params
+        .downcast_ref()
+        .expect("parameters are passed to the solution that parsed them")
+}
+
+/// A solution binary in `src/bin`.
+#[derive(Clone)]
+pub struct Entry {
+    /// Name of the binary, e.g. `01` or `2023-01`.
+    pub bin: String,
+    /// The puzzle the binary solves.
+    pub puzzle: PuzzleId,
+    /// The solution compiled into the registry, `None` if the binary can only be run on its own.
+    pub solution: Option<&'static dyn Solution>,
+}
+
+impl Entry {
+    /// The entry of a solution that was compiled into the registry.
+    pub fn new(bin: impl Into<String>, solution: &'static dyn Solution) -> Self {
+        Self {
+            bin: bin.into(),
+            puzzle: solution.puzzle(),
+            solution: Some(solution),
+        }
+    }
+
+    /// Whether this is an extra binary, i.e. an alternate solution that is not named after the puzzle it solves.
+    /// For example, `src/bin/26.rs` containing `solution!(1)` is an extra binary of day 1.
+    pub fn is_extra(&self) -> bool {
+        self.bin != self.puzzle.to_string()
+    }
+}
+
+/// The set of solutions in `src/bin`, either compiled in by [`register_solutions!`](crate::register_solutions)
+/// so they can be run without spawning `cargo`, or located with [`Registry::discover`].
+#[derive(Clone, Default)]
+pub struct Registry {
+    entries: Vec<Entry>,
+}
+
+impl Registry {
+    pub fn new(entries: Vec<Entry>) -> Self {
+        Self { entries }
+    }
+
+    /// Locates the solutions in `src/bin` by the [`solution!`](crate::solution) invocation in their source,
+    /// without compiling them. Their entries have no solution, they can only be run as their own binaries.
+    pub fn discover() -> Self {
+        let Ok(dir) = fs::read_dir(Path::new("src").join("bin")) else {
+            return Self::default();
+        };
+
+        let mut entries: Vec<Entry> = dir
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+            .filter_map(|path| {
+                let bin = path.file_stem()?.to_str()?.to_string();
+                let puzzle = parse_solution_puzzle(&fs::read_to_string(&path).ok()?)?;
+                Some(Entry {
+                    bin,
+                    puzzle,
+                    solution: None,
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| a.bin.cmp(&b.bin));
+
+        Self::new(entries)
+    }
+
+    /// Whether every solution was compiled into the registry, so they can be run in-process.
+    pub fn is_in_process(&self) -> bool {
+        self.entries.iter().all(|entry| entry.solution.is_some())
+    }
+
+    /// Returns the solution whose binary is named after `puzzle`, e.g. `src/bin/01.rs` for day 1
+    /// or `src/bin/2023-01.rs` for day 1 of 2023.
+    pub fn get(&self, puzzle: PuzzleId) -> Option<&'static dyn Solution> {
+        let bin = puzzle.to_string();
+        self.entries
+            .iter()
+            .find(|entry| entry.bin == bin)
+            .and_then(|entry| entry.solution)
+    }
+
+    /// Returns the extra binaries that solve `puzzle`, ordered by name.
+    pub fn extras(&self, puzzle: PuzzleId) -> impl Iterator<Item = &Entry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.is_extra() && entry.puzzle == puzzle)
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+}
+
+/// The puzzle solved by a solution binary, read from the arguments of the `solution!` invocation in its `source`,
+/// e.g. `solution!(5, year = 2023)`.
+fn parse_solution_puzzle(source: &str) -> Option<PuzzleId> {
+    let (_, rest) = source.split_once("solution!(")?;
+    let args = rest.split_once(");").map_or(rest, |(args, _)| args);
+    let mut args = args.split(',').map(str::trim);
+
+    let day = args.next()?.parse().ok()?;
+    let year = args.find_map(|arg| {
+        arg.strip_prefix("year")?
+            .trim_start()
+            .strip_prefix('=')?
+            .trim()
+            .parse()
+            .ok()
+    });
+
+    Some(PuzzleId::new(year, day))
+}
+
+/// Compiles solution binaries into the current crate as modules and defines `registry()` to look them up.
+///
+/// With the `registry` feature, the main binary invokes this with a list generated from `src/bin` by the build script,
+/// which lets `cargo all` and `cargo time` call `part_one` / `part_two` directly.
+#[macro_export]
+macro_rules! register_solutions {
+    ($( $bin:literal => $module:ident at $path:literal ),* $(,)?) => {
+        $(
+            // lints for solutions are reported when building their own binary.
+            #[path = $path]
+            #[allow(warnings)]
+            mod $module;
+        )*
+
+        /// Every solution in `src/bin`, compiled into this binary.
+        pub fn registry() -> $crate::template::Registry {
+            $crate::template::Registry::new(vec![
+                $( $crate::template::Entry::new($bin, $module::SOLUTION), )*
+            ])
+        }
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::any::Any;
+
+    use super::{parse_solution_puzzle, Entry, Registry, Solution};
+    use crate::{
+        day,
+        template::{Day, PuzzleId, Year},
+        year,
+    };
+
+    struct Mock(Option<Year>, Day);
+
+    impl Solution for Mock {
+        fn day(&self) -> Day {
+            self.1
+        }
+
+        fn year(&self) -> Option<Year> {
+            self.0
+        }
+
+        fn parts(&self) -> &'static [u8] {
+            &[1]
+        }
+
+        fn solve(
+            &self,
+            _variant: Option<&str>,
+            _part: u8,
+            input: &str,
+            _params: &dyn Any,
+        ) -> Option<String> {
+            Some(input.len().to_string())
+        }
+    }
+
+    static DAY_1: Mock = Mock(None, day!(1));
+    static DAY_1_ALT: Mock = Mock(None, day!(1));
+    static DAY_1_2023: Mock = Mock(Some(year!(2023)), day!(1));
+
+    fn get_mock_registry() -> Registry {
+        Registry::new(vec![
+            Entry::new("26", &DAY_1_ALT),
+            Entry::new("01", &DAY_1),
+            Entry::new("2023-01", &DAY_1_2023),
+        ])
+    }
+
+    #[test]
+    fn finds_solution_by_bin_name() {
+        let registry = get_mock_registry();
+        let solution = registry.get(day!(1).into()).unwrap();
+        assert!(std::ptr::addr_eq(solution, &DAY_1));
+        let params = solution.params(&[]).unwrap();
+        assert_eq!(
+            solution.solve(None, 1, "abc", params.as_ref()),
+            Some("3".into())
+        );
+    }
+
+    #[test]
+    fn finds_solution_of_other_year() {
+        let registry = get_mock_registry();
+        let puzzle = PuzzleId::new(Some(year!(2023)), day!(1));
+        let solution = registry.get(puzzle).unwrap();
+        assert!(std::ptr::addr_eq(solution, &DAY_1_2023));
+        assert_eq!(solution.puzzle(), puzzle);
+    }
+
+    #[test]
+    fn finds_extra_binaries() {
+        let registry = get_mock_registry();
+        let extras: Vec<&str> = registry
+            .extras(day!(1).into())
+            .map(|e| e.bin.as_str())
+            .collect();
+        assert_eq!(extras, ["26"]);
+        assert!(registry.extras(day!(2).into()).next().is_none());
+    }
+
+    #[test]
+    fn handles_missing_solutions() {
+        let registry = get_mock_registry();
+        assert!(registry.get(day!(2).into()).is_none());
+    }
+
+    #[test]
+    fn parses_solution_puzzles() {
+        assert_eq!(
+            parse_solution_puzzle("advent_of_code::solution!(5);\n"),
+            Some(day!(5).into())
+        );
+        assert_eq!(
+            parse_solution_puzzle("advent_of_code::solution!(\n    14,\n    params = { rows: i32 = 103 },\n    year = 2023,\n);\n"),
+            Some(PuzzleId::new(Some(year!(2023)), day!(14)))
+        );
+        assert_eq!(parse_solution_puzzle("fn main() {}"), None);
+    }
+
+    #[test]
+    fn discovered_entries_run_on_their_own() {
+        let registry = Registry::new(vec![
+            Entry {
+                bin: "26".into(),
+                puzzle: day!(1).into(),
+                solution: None,
+            },
+            Entry::new("01", &DAY_1),
+        ]);
+
+        assert!(!registry.is_in_process());
+        assert!(registry.extras(day!(1).into()).next().is_some());
+        assert!(get_mock_registry().is_in_process());
+    }
+}
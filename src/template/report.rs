@@ -0,0 +1,719 @@
+/// Machine-readable results of solution runs.
+use std::{collections::HashMap, env, error::Error, fmt::Display, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    limits::LimitExceeded,
+    stats::{stats_from_json, stats_to_json},
+    year::{year_from_json, year_to_json},
+    BenchStats, Day, PuzzleId, Year, ANSI_BOLD, ANSI_RESET,
+};
+
+/// Output formats supported by `solve`, `all` and `time`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable output.
+    #[default]
+    Text,
+    /// A single JSON array holding all records, printed once the run completes.
+    Json,
+    /// One JSON record per line, printed as soon as a part completes.
+    Ndjson,
+}
+
+impl OutputFormat {
+    /// Reads the `--format` argument of the current process, defaulting to [`OutputFormat::Text`].
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        args.iter()
+            .position(|x| x == "--format")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|x| x.parse().ok())
+            .unwrap_or_default()
+    }
+
+    pub fn is_text(self) -> bool {
+        self == Self::Text
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Text => "text",
+            Self::Json => "json",
+            Self::Ndjson => "ndjson",
+        })
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "ndjson" => Ok(Self::Ndjson),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `text`, `json` or `ndjson`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The outcome of running a single part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    Unsolved,
+    /// The part panicked, holding the panic message.
+    Failed(String),
+    /// The run was killed after exceeding its timeout, the duration of the record being the timeout.
+    TimedOut,
+    /// The run was killed after exceeding its memory limit.
+    OutOfMemory,
+}
+
+impl Status {
+    /// Whether the part did not complete, because it panicked or was killed.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Failed(_) | Self::TimedOut | Self::OutOfMemory)
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Solved => "solved",
+            Self::Unsolved => "unsolved",
+            Self::Failed(_) => "failed",
+            Self::TimedOut => "timed_out",
+            Self::OutOfMemory => "out_of_memory",
+        })
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Self::Solved),
+            "unsolved" => Ok(Self::Unsolved),
+            "failed" => Ok(Self::Failed(String::new())),
+            "timed_out" => Ok(Self::TimedOut),
+            "out_of_memory" => Ok(Self::OutOfMemory),
+            _ => Err(format!("unknown status `{s}`.")),
+        }
+    }
+}
+
+/// The result of running a single part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    /// The year of the puzzle, `None` for the default year.
+    pub year: Option<Year>,
+    pub day: Day,
+    /// Name of the extra binary that produced this record, `None` for the puzzle's own binary.
+    pub bin: Option<String>,
+    pub part: u8,
+    /// Name of the variant that produced this record, `None` for the part's default implementation.
+    pub variant: Option<String>,
+    pub answer: Option<String>,
+    pub duration_nanos: u128,
+    pub samples: u128,
+    pub status: Status,
+    /// Benchmark statistics, only present for timed runs.
+    pub stats: Option<BenchStats>,
+}
+
+impl PartRecord {
+    pub fn new(
+        puzzle: impl Into<PuzzleId>,
+        part: u8,
+        answer: Option<String>,
+        duration: Duration,
+        samples: u128,
+    ) -> Self {
+        let puzzle = puzzle.into();
+        let status = if answer.is_some() {
+            Status::Solved
+        } else {
+            Status::Unsolved
+        };
+
+        Self {
+            year: puzzle.year,
+            day: puzzle.day,
+            bin: None,
+            part,
+            variant: None,
+            answer,
+            duration_nanos: duration.as_nanos(),
+            samples,
+            status,
+            stats: None,
+        }
+    }
+
+    /// Creates the record of a solution's shared parse step, which is reported as part `0` without an answer.
+    pub fn parse_step(puzzle: impl Into<PuzzleId>, duration: Duration, samples: u128) -> Self {
+        Self {
+            status: Status::Solved,
+            ..Self::new(puzzle, 0, None, duration, samples)
+        }
+    }
+
+    /// Creates the record of a part that was killed for exceeding `exceeded`, `elapsed` after the run started.
+    pub fn killed(
+        puzzle: impl Into<PuzzleId>,
+        part: u8,
+        exceeded: LimitExceeded,
+        elapsed: Duration,
+    ) -> Self {
+        let (status, duration) = match exceeded {
+            LimitExceeded::Timeout(timeout) => (Status::TimedOut, timeout),
+            LimitExceeded::Memory(_) => (Status::OutOfMemory, elapsed),
+        };
+
+        Self {
+            status,
+            ..Self::new(puzzle, part, None, duration, 1)
+        }
+    }
+
+    /// Creates the record of a part that panicked with the message `reason`, `elapsed` after it started.
+    pub fn failed(
+        puzzle: impl Into<PuzzleId>,
+        part: u8,
+        reason: impl Into<String>,
+        elapsed: Duration,
+    ) -> Self {
+        Self {
+            status: Status::Failed(reason.into()),
+            ..Self::new(puzzle, part, None, elapsed, 1)
+        }
+    }
+
+    /// Describes why the part did not complete, e.g. `timed out after 10s`. `None` for completed parts.
+    pub fn failure(&self) -> Option<String> {
+        match &self.status {
+            Status::Failed(reason) => Some(format!("panicked: {reason}")),
+            Status::TimedOut => Some(format!("timed out after {:?}", self.duration())),
+            Status::OutOfMemory => Some("exceeded the memory limit".into()),
+            Status::Solved | Status::Unsolved => None,
+        }
+    }
+
+    pub fn is_parse_step(&self) -> bool {
+        self.part == 0
+    }
+
+    /// Attaches benchmark statistics, making their median the duration of the record.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn with_stats(mut self, stats: Option<BenchStats>) -> Self {
+        if let Some(stats) = &stats {
+            self.duration_nanos = stats.median_nanos as u128;
+        }
+        self.stats = stats;
+        self
+    }
+
+    /// Marks the record as produced by the extra binary `bin`.
+    #[must_use]
+    pub fn with_bin(mut self, bin: Option<&str>) -> Self {
+        self.bin = bin.map(Into::into);
+        self
+    }
+
+    /// Marks the record as produced by the variant `variant` of its part.
+    #[must_use]
+    pub fn with_variant(mut self, variant: Option<&str>) -> Self {
+        self.variant = variant.map(Into::into);
+        self
+    }
+
+    pub fn puzzle(&self) -> PuzzleId {
+        PuzzleId::new(self.year, self.day)
+    }
+
+    /// Describes the part, e.g. `Part 1`, `Part 1 [fast]` for variants or `Parse` for the parse step.
+    pub fn part_label(&self) -> String {
+        if self.is_parse_step() {
+            return "Parse".into();
+        }
+
+        match &self.variant {
+            Some(variant) => format!("Part {} [{variant}]", self.part),
+            None => format!("Part {}", self.part),
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn duration(&self) -> Duration {
+        Duration::from_nanos(self.duration_nanos as u64)
+    }
+
+    /// Serializes the record to a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        JsonValue::from(self).stringify().unwrap()
+    }
+}
+
+/// Serializes a set of records to a JSON array.
+pub fn records_to_json(records: &[PartRecord]) -> String {
+    JsonValue::Array(records.iter().map(JsonValue::from).collect())
+        .stringify()
+        .unwrap()
+}
+
+/// Returns pairs of `(default, variant)` records where a variant answered differently than the default
+/// implementation of the same part. Parts that are unsolved by either are skipped.
+pub fn find_disagreements(records: &[PartRecord]) -> Vec<(&PartRecord, &PartRecord)> {
+    records
+        .iter()
+        .filter(|record| record.variant.is_some() && record.answer.is_some())
+        .filter_map(|variant| {
+            let default = records.iter().find(|record| {
+                record.variant.is_none()
+                    && record.part == variant.part
+                    && record.puzzle() == variant.puzzle()
+                    && record.bin == variant.bin
+            })?;
+
+            (default.answer.is_some() && default.answer != variant.answer)
+                .then_some((default, variant))
+        })
+        .collect()
+}
+
+/// Describes how the answers of `current` changed since `previous`, e.g. `Part 1: 10 → 11`.
+/// Parts that answer the same as before are left out, parts new to `current` are listed with their answer.
+pub fn diff_answers(previous: &[PartRecord], current: &[PartRecord]) -> Vec<String> {
+    let describe = |answer: &Option<String>| answer.clone().unwrap_or_else(|| "✘".into());
+
+    current
+        .iter()
+        .filter(|record| !record.is_parse_step())
+        .filter_map(|record| {
+            let before = previous.iter().find(|before| {
+                before.puzzle() == record.puzzle()
+                    && before.part == record.part
+                    && before.variant == record.variant
+                    && before.bin == record.bin
+            });
+
+            match before {
+                Some(before) if before.answer == record.answer => None,
+                Some(before) => Some(format!(
+                    "{}: {} → {}",
+                    record.part_label(),
+                    describe(&before.answer),
+                    describe(&record.answer)
+                )),
+                None => Some(format!(
+                    "{}: {} (new)",
+                    record.part_label(),
+                    describe(&record.answer)
+                )),
+            }
+        })
+        .collect()
+}
+
+/// Tally of the days of a run of several puzzles: days where every part was solved, days where a part
+/// failed and days that are not (fully) solved yet.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RunSummary {
+    pub passed: Vec<String>,
+    pub failed: Vec<String>,
+    pub unsolved: Vec<String>,
+}
+
+impl RunSummary {
+    /// Adds the day `name`, e.g. `01`, given the records it reported or `None` if it did not run.
+    pub fn add(&mut self, name: String, records: Option<&[PartRecord]>) {
+        let records = records.unwrap_or_default();
+
+        if records.iter().any(|record| record.status.is_failure()) {
+            self.failed.push(name);
+        } else if records.iter().any(|record| !record.is_parse_step())
+            && records.iter().all(|record| record.status == Status::Solved)
+        {
+            self.passed.push(name);
+        } else {
+            self.unsolved.push(name);
+        }
+    }
+
+    pub fn has_failures(&self) -> bool {
+        !self.failed.is_empty()
+    }
+
+    /// Prints the counts, e.g. `Summary: 20 passed, 2 failed, 3 unsolved`, followed by the failed days.
+    pub fn print(&self) {
+        println!(
+            "{ANSI_BOLD}Summary:{ANSI_RESET} {} passed, {} failed, {} unsolved",
+            self.passed.len(),
+            self.failed.len(),
+            self.unsolved.len()
+        );
+
+        if self.has_failures() {
+            println!("Failed: {}", self.failed.join(", "));
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartRecord> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("year".into(), year_to_json(value.year));
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "bin".into(),
+            value.bin.clone().map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert(
+            "variant".into(),
+            value
+                .variant
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.duration_nanos as f64),
+        );
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("status".into(), JsonValue::String(value.status.to_string()));
+        if let Status::Failed(reason) = &value.status {
+            map.insert("reason".into(), JsonValue::String(reason.clone()));
+        }
+        map.insert("stats".into(), stats_to_json(value.stats.as_ref()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let year = year_from_json(json, "record")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        let bin = json.get("bin").and_then(|v| v.get::<String>()).cloned();
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected record.part to be a number.")?;
+
+        let variant = json.get("variant").and_then(|v| v.get::<String>()).cloned();
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.answer to be null or string.")?;
+
+        let duration_nanos = json
+            .get("duration_nanos")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u128)
+            .ok_or("Expected record.duration_nanos to be a number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u128)
+            .ok_or("Expected record.samples to be a number.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.status to be a string.")?
+            .parse()
+            .map(|status| match status {
+                Status::Failed(_) => Status::Failed(
+                    json.get("reason")
+                        .and_then(|v| v.get::<String>())
+                        .cloned()
+                        .unwrap_or_default(),
+                ),
+                status => status,
+            })?;
+
+        let stats = stats_from_json(json, "stats")?;
+
+        Ok(PartRecord {
+            year,
+            day,
+            bin,
+            part,
+            variant,
+            answer: answer.cloned(),
+            duration_nanos,
+            samples,
+            status,
+            stats,
+        })
+    }
+}
+
+impl TryFrom<&str> for PartRecord {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+        PartRecord::try_from(&json)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        diff_answers, find_disagreements, records_to_json, OutputFormat, PartRecord, RunSummary,
+        Status,
+    };
+    use crate::{
+        day,
+        template::{limits::LimitExceeded, PuzzleId},
+        year,
+    };
+
+    #[test]
+    fn parses_output_formats() {
+        assert_eq!("text".parse::<OutputFormat>().unwrap(), OutputFormat::Text);
+        assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!(
+            "ndjson".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Ndjson
+        );
+        assert!("yaml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn derives_status_from_answer() {
+        let solved = PartRecord::new(day!(1), 1, Some("42".into()), Duration::ZERO, 1);
+        let unsolved = PartRecord::new(day!(1), 2, None, Duration::ZERO, 1);
+        assert_eq!(solved.status, Status::Solved);
+        assert_eq!(unsolved.status, Status::Unsolved);
+    }
+
+    #[test]
+    fn describes_killed_parts() {
+        let timed_out = PartRecord::killed(
+            day!(14),
+            2,
+            LimitExceeded::Timeout(Duration::from_secs(10)),
+            Duration::from_millis(10_020),
+        );
+        assert_eq!(timed_out.status, Status::TimedOut);
+        assert_eq!(timed_out.failure().unwrap(), "timed out after 10s");
+
+        let parsed = PartRecord::try_from(timed_out.to_json_line().as_str()).unwrap();
+        assert_eq!(parsed, timed_out);
+
+        let out_of_memory = PartRecord::killed(
+            day!(14),
+            1,
+            LimitExceeded::Memory(512 << 20),
+            Duration::from_millis(300),
+        );
+        assert_eq!(out_of_memory.status, Status::OutOfMemory);
+        assert_eq!(out_of_memory.duration(), Duration::from_millis(300));
+        assert!(out_of_memory.status.is_failure());
+        assert!(PartRecord::new(day!(14), 1, None, Duration::ZERO, 1)
+            .failure()
+            .is_none());
+    }
+
+    #[test]
+    fn summarizes_days() {
+        let solved = PartRecord::new(day!(1), 1, Some("11".into()), Duration::ZERO, 1);
+        let unsolved = PartRecord::new(day!(1), 2, None, Duration::ZERO, 1);
+        let failed = PartRecord::failed(day!(1), 2, "at the disco", Duration::ZERO);
+        let parse = PartRecord::parse_step(day!(1), Duration::ZERO, 1);
+
+        let mut summary = RunSummary::default();
+        summary.add("01".into(), Some(&[solved.clone()]));
+        summary.add("02".into(), Some(&[solved.clone(), unsolved]));
+        summary.add("03".into(), Some(&[solved, failed]));
+        summary.add("04".into(), None);
+        summary.add("05".into(), Some(&[parse]));
+
+        assert_eq!(summary.passed, ["01"]);
+        assert_eq!(summary.failed, ["03"]);
+        assert_eq!(summary.unsolved, ["02", "04", "05"]);
+        assert!(summary.has_failures());
+    }
+
+    #[test]
+    fn roundtrips_failed_parts() {
+        let failed = PartRecord::failed(day!(16), 2, "at the disco", Duration::from_millis(3));
+        assert_eq!(failed.failure().unwrap(), "panicked: at the disco");
+        assert!(failed.status.is_failure());
+
+        let parsed = PartRecord::try_from(failed.to_json_line().as_str()).unwrap();
+        assert_eq!(parsed.status, Status::Failed("at the disco".into()));
+    }
+
+    #[test]
+    fn roundtrips_json_lines() {
+        let record = PartRecord::new(
+            day!(3),
+            2,
+            Some("a,b".into()),
+            Duration::from_nanos(74130),
+            100_000,
+        );
+        let parsed = PartRecord::try_from(record.to_json_line().as_str()).unwrap();
+        assert_eq!(parsed, record);
+    }
+
+    #[test]
+    fn roundtrips_years() {
+        let record = PartRecord::new(
+            PuzzleId::new(Some(year!(2023)), day!(3)),
+            1,
+            None,
+            Duration::ZERO,
+            1,
+        );
+        let parsed = PartRecord::try_from(record.to_json_line().as_str()).unwrap();
+        assert_eq!(parsed.puzzle().to_string(), "2023-03");
+    }
+
+    #[test]
+    fn roundtrips_extra_binaries() {
+        let record = PartRecord::new(day!(1), 1, None, Duration::ZERO, 1).with_bin(Some("26"));
+        let parsed = PartRecord::try_from(record.to_json_line().as_str()).unwrap();
+        assert_eq!(parsed.bin, Some("26".into()));
+    }
+
+    #[test]
+    fn roundtrips_variants() {
+        let record =
+            PartRecord::new(day!(1), 1, None, Duration::ZERO, 1).with_variant(Some("fast"));
+        let parsed = PartRecord::try_from(record.to_json_line().as_str()).unwrap();
+        assert_eq!(parsed.variant, Some("fast".into()));
+        assert_eq!(parsed.part_label(), "Part 1 [fast]");
+    }
+
+    #[test]
+    fn roundtrips_parse_steps() {
+        let record = PartRecord::parse_step(day!(1), Duration::from_nanos(1200), 1);
+        let parsed = PartRecord::try_from(record.to_json_line().as_str()).unwrap();
+        assert_eq!(parsed.status, Status::Solved);
+        assert_eq!(parsed.answer, None);
+        assert_eq!(parsed.part_label(), "Parse");
+    }
+
+    #[test]
+    fn finds_disagreeing_variants() {
+        let record = |part: u8, answer: Option<&str>, variant: Option<&str>| {
+            PartRecord::new(day!(1), part, answer.map(Into::into), Duration::ZERO, 1)
+                .with_variant(variant)
+        };
+
+        let records = [
+            record(1, Some("1"), None),
+            record(2, Some("2"), None),
+            record(1, Some("1"), Some("same")),
+            record(1, Some("3"), Some("wrong")),
+            record(2, None, Some("unsolved")),
+        ];
+
+        let disagreements = find_disagreements(&records);
+        assert_eq!(disagreements.len(), 1);
+        assert_eq!(disagreements[0].0.answer, Some("1".into()));
+        assert_eq!(disagreements[0].1.variant, Some("wrong".into()));
+    }
+
+    #[test]
+    fn diffs_answers() {
+        let record = |part: u8, answer: Option<&str>| {
+            PartRecord::new(day!(1), part, answer.map(Into::into), Duration::ZERO, 1)
+        };
+
+        let previous = [record(1, Some("10")), record(2, None)];
+        let current = [
+            PartRecord::parse_step(day!(1), Duration::ZERO, 1),
+            record(1, Some("11")),
+            record(2, None),
+            record(2, Some("31")).with_variant(Some("fast")),
+        ];
+
+        assert_eq!(
+            diff_answers(&previous, &current),
+            ["Part 1: 10 → 11", "Part 2 [fast]: 31 (new)"]
+        );
+        assert!(diff_answers(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn handles_missing_answers() {
+        let line = r#"{ "day": "01", "part": 1, "answer": null, "duration_nanos": 10, "samples": 1, "status": "unsolved" }"#;
+        let record = PartRecord::try_from(line).unwrap();
+        assert_eq!(record.answer, None);
+        assert_eq!(record.status, Status::Unsolved);
+    }
+
+    #[test]
+    fn errors_for_text_lines() {
+        assert!(PartRecord::try_from("Part 1: 42 (1.0ms)").is_err());
+    }
+
+    #[test]
+    fn serializes_record_arrays() {
+        let records = [
+            PartRecord::new(day!(1), 1, Some("1".into()), Duration::ZERO, 1),
+            PartRecord::new(day!(1), 2, None, Duration::ZERO, 1),
+        ];
+        let json = records_to_json(&records);
+        assert!(json.starts_with('['));
+        assert_eq!(json.matches("\"part\"").count(), 2);
+    }
+}
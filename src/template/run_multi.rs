@@ -1,52 +1,314 @@
-use std::{collections::HashSet, io};
-
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::{
+    collections::{HashMap, HashSet},
+    io, iter,
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, Receiver},
+        Arc,
+    },
+    thread,
+};
 
-use super::{
-    all_days,
-    timings::{Timing, Timings},
+use crate::template::{
+    limits::Limits, records_to_json, runner, try_read_file, OutputFormat, PartRecord, PuzzleId,
+    Registry, RunSummary, Solution, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+use super::timings::{Timing, Timings};
+use child_commands::Builds;
+
+/// Run a set of puzzles, either in-process via the solution `registry` or, if `is_isolated` is set,
+/// as one child process per day. Child processes are built with a single `cargo build` up front and
+/// run from `target/`, days that do not compile are reported before anything runs.
+/// Runs with `limits` are always isolated, as only child processes can be killed, and so are runs of
+/// a registry whose solutions were not compiled in, see [`Registry::is_in_process`].
+///
+/// With more than one of `jobs`, up to `jobs` days run at once as child processes. Their output is
+/// collected and printed in day order once each day completes.
+#[allow(clippy::too_many_arguments)]
+pub fn run_multi(
+    registry: &Registry,
+    days_to_run: &HashSet<PuzzleId>,
+    is_release: bool,
+    is_timed: bool,
+    is_isolated: bool,
+    jobs: usize,
+    limits: Limits,
+    format: OutputFormat,
+) -> (RunSummary, Option<Timings>) {
+    let is_parallel = jobs > 1;
+    let is_isolated = is_isolated || is_parallel || limits.is_set() || !registry.is_in_process();
+
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut records: Vec<PartRecord> = vec![];
+    let mut summary = RunSummary::default();
 
     let mut need_space = false;
-    println!("Running {:?} days", days_to_run);
-    println!("{:?}",   all_days()
-        .filter(|day| days_to_run.contains(day)).count());
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            println!("Running day {}", day);
-            if need_space {
-                println!();
+
+    // NOTE: run puzzles sorted, with the default year first.
+    let mut days_to_run: Vec<PuzzleId> = days_to_run.iter().copied().collect();
+    days_to_run.sort_unstable();
+
+    let runs = get_runs(registry, &days_to_run);
+
+    let builds = if is_isolated {
+        build_runs(&runs, is_release)
+    } else {
+        Builds::default()
+    };
+
+    thread::scope(|scope| {
+        let collected =
+            is_parallel.then(|| spawn_workers(scope, &runs, &builds, jobs, is_timed, limits));
+        let mut pending = HashMap::new();
+
+        for (i, &(puzzle, solution, bin)) in runs.iter().enumerate() {
+            let bin_name = get_bin_name(puzzle, bin);
+            let is_build_failed = builds.is_failed(&bin_name);
+
+            if format.is_text() {
+                if need_space {
+                    println!();
+                }
+                need_space = true;
+
+                match bin {
+                    Some(bin) => println!("{ANSI_BOLD}Day {puzzle}{ANSI_RESET} (extra: {bin})"),
+                    None => println!("{ANSI_BOLD}Day {puzzle}{ANSI_RESET}"),
+                }
+                println!("------");
             }
-            need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+            let run = if let Some(collected) = &collected {
+                // days finish in any order, hold on to those that finished before the current one.
+                while !pending.contains_key(&i) {
+                    let (j, run) = collected.recv().expect("every run is sent by a worker");
+                    pending.insert(j, run);
+                }
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+                pending
+                    .remove(&i)
+                    .unwrap()
+                    .map(|run| run.and_then(|run| run.report(puzzle, bin, solution, format)))
+            } else if is_isolated {
+                builds
+                    .executable(&bin_name)
+                    .map(|executable| {
+                        child_commands::run_solution(
+                            puzzle, bin, solution, executable, is_timed, limits, format,
+                        )
+                    })
+                    .transpose()
+                    .map(Option::flatten)
+            } else {
+                Ok(solution
+                    .and_then(|solution| run_in_process(solution, puzzle, bin, is_timed, format)))
+            };
+
+            // a solution that could not be run counts as failed, like a panicking one.
+            let (day_records, is_run_failed) = match run {
+                Ok(day_records) => (day_records, false),
+                Err(e) => {
+                    eprintln!("✘ Failed to run the solution: {e:?}");
+                    (None, true)
+                }
+            };
 
-            if output.is_empty() {
-                println!("Not solved.");
+            if is_build_failed || is_run_failed {
+                summary.failed.push(bin_name);
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+                summary.add(bin_name, day_records.as_deref());
+            }
+
+            match day_records {
+                Some(day_records) => {
+                    // child processes report disagreeing variants on their own.
+                    if !is_isolated {
+                        runner::print_disagreements(&day_records);
+                    }
+                    timings.extend(Timing::from_run(puzzle, bin, &day_records));
+                    records.extend(day_records);
+                }
+                None if is_run_failed => {}
+                None => {
+                    if format.is_text() {
+                        if is_build_failed {
+                            println!("✘ Could not be built, see the errors above.");
+                        } else {
+                            println!("Not solved.");
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    if format == OutputFormat::Json {
+        println!("{}", records_to_json(&records));
+    }
+
+    let timings = is_timed.then_some(Timings { data: timings });
+
+    if format.is_text() {
+        if let Some(timings) = &timings {
+            let total_millis = timings.total_millis();
+            println!(
+                "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
+        println!();
+        summary.print();
+    }
+
+    (summary, timings)
+}
+
+/// A binary to run: the puzzle it solves, its solution if it is in the registry and the name of the extra binary if it is one.
+pub(crate) type Run<'a> = (PuzzleId, Option<&'a dyn Solution>, Option<&'a str>);
+
+/// The binaries solving `puzzles`. A day's own binary runs first, followed by extra binaries that solve the same puzzle.
+pub(crate) fn get_runs<'a>(registry: &'a Registry, puzzles: &[PuzzleId]) -> Vec<Run<'a>> {
+    puzzles
+        .iter()
+        .flat_map(|&puzzle| {
+            iter::once((puzzle, registry.get(puzzle), None)).chain(
+                registry
+                    .extras(puzzle)
+                    .map(move |entry| (puzzle, entry.solution, Some(entry.bin.as_str()))),
+            )
+        })
+        .collect()
+}
+
+/// Builds the binaries of `runs` with a single `cargo build` and prints the errors of those that don't compile.
+/// Runs without a binary in `src/bin` are skipped. If `cargo` can't be run at all, every binary counts as failed.
+pub(crate) fn build_runs(runs: &[Run], is_release: bool) -> Builds {
+    let bins: Vec<String> = runs
+        .iter()
+        .map(|&(puzzle, _, bin)| get_bin_name(puzzle, bin))
+        .filter(|bin| Path::new(&get_path_for_bin(bin)).exists())
+        .collect();
+
+    match child_commands::build_bins(&bins, is_release) {
+        Ok(builds) => {
+            builds.print_errors();
+            builds
+        }
+        Err(e) => {
+            eprintln!("✘ Failed to build the solutions: {e:?}");
+            Builds::failed(bins)
+        }
+    }
+}
+
+/// Spawns `jobs` workers that run the bins of `runs` as child processes, taking the next run once they finish one.
+/// Every finished run is sent with its index in `runs`, runs that could not be started with their error.
+fn spawn_workers<'scope>(
+    scope: &'scope thread::Scope<'scope, '_>,
+    runs: &'scope [Run<'scope>],
+    builds: &'scope Builds,
+    jobs: usize,
+    is_timed: bool,
+    limits: Limits,
+) -> Receiver<(usize, Result<Option<child_commands::CollectedRun>, Error>)> {
+    let (sender, receiver) = mpsc::channel();
+    let next = Arc::new(AtomicUsize::new(0));
+
+    for _ in 0..jobs.min(runs.len()) {
+        let sender = sender.clone();
+        let next = Arc::clone(&next);
+
+        scope.spawn(move || loop {
+            let i = next.fetch_add(1, Ordering::Relaxed);
+            let Some(&(puzzle, _, bin)) = runs.get(i) else {
+                break;
+            };
+
+            let run = builds
+                .executable(&get_bin_name(puzzle, bin))
+                .map(|executable| child_commands::collect_solution(executable, is_timed, limits))
+                .transpose();
+
+            if sender.send((i, run)).is_err() {
+                break;
             }
         });
+    }
 
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
-        Some(timings)
-    } else {
-        None
+    receiver
+}
+
+fn run_in_process(
+    solution: &dyn Solution,
+    puzzle: PuzzleId,
+    bin: Option<&str>,
+    is_timed: bool,
+    format: OutputFormat,
+) -> Option<Vec<PartRecord>> {
+    let input = match try_read_file("inputs", puzzle) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Could not open input file: {e}");
+            return None;
+        }
+    };
+
+    let mut records = vec![];
+
+    // variants run after the default implementations, so they can be compared side by side.
+    let parts = solution.parts().iter().map(|&part| (None, part));
+    let variants = solution
+        .variants()
+        .iter()
+        .map(|&(variant, part)| (Some(variant), part));
+
+    // solutions with a parse step parse their input once, every part then solves the parsed input.
+    let parsed = match solution.parser() {
+        Some(parse) => {
+            let (parsed, record) =
+                runner::run_parse_reported(parse, &input, puzzle, is_timed, format);
+            let record = record.with_bin(bin);
+            runner::print_record(&record, format);
+            records.push(record);
+
+            // the parts can't run without their parsed input.
+            if parsed.is_none() {
+                return Some(records);
+            }
+            parsed
+        }
+        None => None,
+    };
+
+    // parameters are parsed up front, so parsing them is not part of the measured time.
+    let params = solution
+        .params(&[])
+        .expect("the default parameters are valid");
+
+    // a panicking part is recorded as failed and does not take down the remaining parts with it.
+    for (variant, part) in parts.chain(variants) {
+        let record = runner::run_reported(
+            |input: &str| match (&parsed, variant) {
+                (Some(parsed), variant) => {
+                    solution.solve_parsed(variant, part, parsed.as_ref(), params.as_ref())
+                }
+                (None, variant) => solution.solve(variant, part, input, params.as_ref()),
+            },
+            input.as_str(),
+            puzzle,
+            part,
+            is_timed,
+            format,
+        )
+        .with_bin(bin)
+        .with_variant(variant);
+
+        runner::print_record(&record, format);
+        records.push(record);
     }
+
+    Some(records)
 }
 
 #[allow(dead_code)]
@@ -63,198 +325,524 @@ impl From<std::io::Error> for Error {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(bin: &str) -> String {
+    format!("./src/bin/{bin}.rs")
+}
+
+/// The name of the bin solving `puzzle`, or the extra binary `bin` if set.
+pub(crate) fn get_bin_name(puzzle: PuzzleId, bin: Option<&str>) -> String {
+    bin.map_or_else(|| puzzle.to_string(), Into::into)
+}
+
+/// The part a killed run was working on: the first of the parse step, the parts and the variants of `solution`
+/// that did not report a record yet. Without a `solution`, the part after the last reported one is assumed.
+fn get_killed_part<'a>(
+    solution: Option<&'a dyn Solution>,
+    records: &[PartRecord],
+) -> (u8, Option<&'a str>) {
+    let is_reported = |part: u8, variant: Option<&str>| {
+        records
+            .iter()
+            .any(|record| record.part == part && record.variant.as_deref() == variant)
+    };
+
+    let Some(solution) = solution else {
+        let last = records.iter().map(|record| record.part).max().unwrap_or(0);
+        return ((last + 1).min(2), None);
+    };
+
+    let parse = solution.parser().map(|_| (0, None));
+    let parts = solution.parts().iter().map(|&part| (part, None));
+    let variants = solution
+        .variants()
+        .iter()
+        .map(|&(variant, part)| (part, Some(variant)));
+
+    parse
+        .into_iter()
+        .chain(parts)
+        .chain(variants)
+        .find(|&(part, variant)| !is_reported(part, variant))
+        .unwrap_or((1, None))
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading the records they report.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{get_killed_part, Error};
+    use crate::template::{
+        limits::{LimitExceeded, Limits, Watchdog},
+        runner, OutputFormat, PartRecord, PuzzleId, Solution,
+    };
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
+        collections::{BTreeMap, HashMap},
+        io::{BufRead, BufReader, Read},
+        path::{Path, PathBuf},
         process::{Command, Stdio},
+        str::FromStr,
+        sync::mpsc::{self, RecvTimeoutError, Sender},
         thread,
+        time::{Duration, Instant},
     };
+    use tinyjson::JsonValue;
+
+    /// A line printed by a solution bin.
+    pub enum OutputLine {
+        Stdout(String),
+        Stderr(String),
+    }
+
+    /// The limit a run was killed for and how long it ran until then.
+    type Killed = (LimitExceeded, Duration);
+
+    /// The result of building a set of solution bins with [`build_bins`].
+    #[derive(Debug, Default)]
+    pub struct Builds {
+        /// Paths of the compiled binaries in `target/`, by name of the bin.
+        executables: HashMap<String, PathBuf>,
+        /// Bins that were built but did not compile.
+        failed: Vec<String>,
+        /// Rendered compiler errors, by name of the target they occurred in.
+        errors: BTreeMap<String, Vec<String>>,
+    }
+
+    impl Builds {
+        /// The result of a build in which none of `bins` compiled.
+        pub fn failed(bins: Vec<String>) -> Self {
+            Self {
+                failed: bins,
+                ..Self::default()
+            }
+        }
+
+        /// The path of the compiled binary of `bin`, `None` if it was not built or did not compile.
+        pub fn executable(&self, bin: &str) -> Option<&Path> {
+            self.executables.get(bin).map(PathBuf::as_path)
+        }
+
+        pub fn is_failed(&self, bin: &str) -> bool {
+            self.failed.iter().any(|failed| failed == bin)
+        }
+
+        /// Prints the compiler errors of every target that did not compile.
+        pub fn print_errors(&self) {
+            for (target, errors) in &self.errors {
+                eprintln!("✘ Could not compile `{target}`:");
+                for error in errors {
+                    eprintln!("{}", error.trim_end());
+                }
+                eprintln!();
+            }
+        }
+    }
+
+    /// Builds `bins` with a single `cargo build`, so cargo checks the dependencies once instead of once per day.
+    /// Bins that don't compile don't stop the others from being built.
+    pub fn build_bins(bins: &[String], is_release: bool) -> Result<Builds, Error> {
+        build_bins_in(Path::new("."), bins, is_release)
+    }
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    /// Builds `bins` of the package in `dir`, see [`build_bins`].
+    pub(super) fn build_bins_in(
+        dir: &Path,
+        bins: &[String],
+        is_release: bool,
+    ) -> Result<Builds, Error> {
+        if bins.is_empty() {
+            return Ok(Builds::default());
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut args = vec![
+            "build",
+            "--quiet",
+            "--keep-going",
+            "--message-format",
+            "json",
+        ];
 
         if is_release {
             args.push("--release");
         }
 
+        for bin in bins {
+            args.extend(["--bin", bin]);
+        }
+
+        let output = Command::new("cargo")
+            .args(&args)
+            .current_dir(dir)
+            .output()?;
+        let mut builds = parse_build_messages(&String::from_utf8_lossy(&output.stdout));
+
+        // compiler errors are reported per day, anything else cargo complains about is shown as it is.
+        if !output.status.success() && builds.errors.is_empty() {
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+        }
+        builds.failed = bins
+            .iter()
+            .filter(|bin| !builds.executables.contains_key(*bin))
+            .cloned()
+            .collect();
+
+        Ok(builds)
+    }
+
+    /// Collects the compiled binaries and the compiler errors from the JSON messages of `cargo build`.
+    pub(super) fn parse_build_messages(output: &str) -> Builds {
+        let mut builds = Builds::default();
+
+        for message in output
+            .lines()
+            .filter_map(|line| JsonValue::from_str(line).ok())
+        {
+            let target = get_string(&message, &["target", "name"]);
+
+            match get_string(&message, &["reason"]).as_deref() {
+                Some("compiler-artifact") => {
+                    if let (Some(target), Some(executable)) =
+                        (target, get_string(&message, &["executable"]))
+                    {
+                        builds.executables.insert(target, executable.into());
+                    }
+                }
+                Some("compiler-message") => {
+                    let is_error = get_string(&message, &["message", "level"])
+                        .is_some_and(|level| level == "error");
+
+                    if let (true, Some(target), Some(rendered)) = (
+                        is_error,
+                        target,
+                        get_string(&message, &["message", "rendered"]),
+                    ) {
+                        builds.errors.entry(target).or_default().push(rendered);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        builds
+    }
+
+    /// The string at `path` in nested objects, `None` if a key is missing or the value is no string.
+    fn get_string(json: &JsonValue, path: &[&str]) -> Option<String> {
+        path.iter()
+            .try_fold(json, |json, key| {
+                json.get::<HashMap<String, JsonValue>>()?.get(*key)
+            })?
+            .get::<String>()
+            .cloned()
+    }
+
+    /// Run the compiled solution bin `executable` for a given puzzle, or the extra binary `bin` solving it,
+    /// and collect the records it reports. Returns `None` if it did not report anything.
+    ///
+    /// A run exceeding `limits` is killed, the part it was working on is reported as timed out or out of memory.
+    /// `solution` is used to tell which part that was.
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        extra_bin: Option<&str>,
+        solution: Option<&dyn Solution>,
+        executable: &Path,
+        is_timed: bool,
+        limits: Limits,
+        format: OutputFormat,
+    ) -> Result<Option<Vec<PartRecord>>, Error> {
+        // forward output to stdout/stderr while grabbing records.
+        let mut report = Report::new(puzzle, extra_bin, format);
+        let killed = run_bin(executable, is_timed, None, limits, |line| {
+            report.add_line(line);
+        })?;
+
+        Ok(report.finish(solution, killed))
+    }
+
+    /// Run `command`, e.g. `cargo run` of a solution bin that reports its records as NDJSON, and print the records in `format`
+    /// like [`run_solution`]. A run exceeding `limits` is killed, the part after the last reported one is reported as
+    /// timed out or out of memory.
+    pub fn run_command(
+        puzzle: PuzzleId,
+        extra_bin: Option<&str>,
+        command: Command,
+        limits: Limits,
+        format: OutputFormat,
+    ) -> Result<Option<Vec<PartRecord>>, Error> {
+        let mut report = Report::new(puzzle, extra_bin, format);
+        let killed = run_watched(command, limits, |line| {
+            report.add_line(line);
+        })?;
+
+        Ok(report.finish(None, killed))
+    }
+
+    /// Run the compiled solution bin like [`run_solution`], but collect its output instead of reporting it right away.
+    /// This allows running several bins at once and reporting them in order with [`CollectedRun::report`].
+    pub fn collect_solution(
+        executable: &Path,
+        is_timed: bool,
+        limits: Limits,
+    ) -> Result<CollectedRun, Error> {
+        let mut lines = vec![];
+        let killed = run_bin(executable, is_timed, None, limits, |line| lines.push(line))?;
+
+        Ok(CollectedRun { lines, killed })
+    }
+
+    /// Run the compiled solution bin against the example named `example`, or the puzzle input if `None`,
+    /// and collect the records it reports without printing them. Parts killed for exceeding `limits` are
+    /// recorded as in [`run_solution`].
+    pub fn check_solution(
+        puzzle: PuzzleId,
+        extra_bin: Option<&str>,
+        solution: Option<&dyn Solution>,
+        executable: &Path,
+        example: Option<&str>,
+        limits: Limits,
+    ) -> Result<Option<Vec<PartRecord>>, Error> {
+        // records are not printed in JSON format until the run is finished, which is left to the caller.
+        let mut report = Report::new(puzzle, extra_bin, OutputFormat::Json);
+        let killed = run_bin(executable, false, example, limits, |line| {
+            report.add_line(line);
+        })?;
+
+        Ok(report.finish(solution, killed))
+    }
+
+    /// The output of a solution bin, see [`collect_solution`].
+    pub struct CollectedRun {
+        lines: Vec<OutputLine>,
+        killed: Option<Killed>,
+    }
+
+    impl CollectedRun {
+        /// Prints the output of the run and collects the records it reported, as [`run_solution`] does while running.
+        pub fn report(
+            self,
+            puzzle: PuzzleId,
+            extra_bin: Option<&str>,
+            solution: Option<&dyn Solution>,
+            format: OutputFormat,
+        ) -> Option<Vec<PartRecord>> {
+            let mut report = Report::new(puzzle, extra_bin, format);
+            for line in self.lines {
+                report.add_line(line);
+            }
+            report.finish(solution, self.killed)
+        }
+    }
+
+    /// Prints the output of a solution bin and collects the records it reports.
+    struct Report<'a> {
+        puzzle: PuzzleId,
+        extra_bin: Option<&'a str>,
+        format: OutputFormat,
+        records: Vec<PartRecord>,
+    }
+
+    impl<'a> Report<'a> {
+        fn new(puzzle: PuzzleId, extra_bin: Option<&'a str>, format: OutputFormat) -> Self {
+            Self {
+                puzzle,
+                extra_bin,
+                format,
+                records: vec![],
+            }
+        }
+
+        fn add_line(&mut self, line: OutputLine) {
+            let line = match line {
+                OutputLine::Stdout(line) => line,
+                OutputLine::Stderr(line) => {
+                    eprintln!("{line}");
+                    return;
+                }
+            };
+
+            match PartRecord::try_from(line.as_str()) {
+                Ok(record) => {
+                    let record = record.with_bin(self.extra_bin);
+                    runner::print_record(&record, self.format);
+                    self.records.push(record);
+                }
+                // keep machine-readable output clean of anything the solution printed.
+                Err(_) if self.format.is_text() => println!("{line}"),
+                Err(_) => eprintln!("{line}"),
+            }
+        }
+
+        fn finish(
+            mut self,
+            solution: Option<&dyn Solution>,
+            killed: Option<Killed>,
+        ) -> Option<Vec<PartRecord>> {
+            if let Some((exceeded, elapsed)) = killed {
+                let (part, variant) = get_killed_part(solution, &self.records);
+                let record = PartRecord::killed(self.puzzle, part, exceeded, elapsed)
+                    .with_bin(self.extra_bin)
+                    .with_variant(variant);
+                runner::print_record(&record, self.format);
+                self.records.push(record);
+            }
+
+            // a child that reported nothing, e.g. because of a missing input file, did not solve the day.
+            (!self.records.is_empty()).then_some(self.records)
+        }
+    }
+
+    /// Runs the compiled bin `executable` against the example named `example`, or the puzzle input if `None`,
+    /// passing every line it prints to `on_line` as soon as it is printed.
+    /// Returns the limit the run was killed for, if any.
+    fn run_bin(
+        executable: &Path,
+        is_timed: bool,
+        example: Option<&str>,
+        limits: Limits,
+        on_line: impl FnMut(OutputLine),
+    ) -> Result<Option<Killed>, Error> {
+        // children always report records, which are then rendered in the requested format.
+        let mut args = vec!["--format", "ndjson", "--all-variants"];
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        if let Some(example) = example {
+            args.extend(["--example", example]);
+        }
+
+        let mut command = Command::new(executable);
+        command.args(&args);
+        run_watched(command, limits, on_line)
+    }
+
+    /// Runs `command`, passing every line it prints to `on_line` as soon as it is printed, and kills it once it exceeds `limits`.
+    /// Returns the limit the run was killed for, if any.
+    fn run_watched(
+        mut command: Command,
+        limits: Limits,
+        mut on_line: impl FnMut(OutputLine),
+    ) -> Result<Option<Killed>, Error> {
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
+        let started = Instant::now();
+        let mut watchdog = Watchdog::new(limits);
+
+        let mut cmd = command
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let stdout = cmd.stdout.take().ok_or(super::Error::BrokenPipe)?;
+        let stderr = cmd.stderr.take().ok_or(super::Error::BrokenPipe)?;
 
-        let mut output = vec![];
+        // both streams are read on their own threads, so the limits can be checked while the child is quiet.
+        let (sender, receiver) = mpsc::channel();
+        let stdout_thread = forward_lines(stdout, sender.clone(), OutputLine::Stdout);
+        let stderr_thread = forward_lines(stderr, sender, OutputLine::Stderr);
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        let mut exceeded = None;
+
+        loop {
+            match receiver.recv_timeout(Duration::from_millis(10)) {
+                Ok(line) => on_line(line),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            if exceeded.is_none() {
+                exceeded = watchdog.check(&mut cmd);
+            }
         }
 
-        thread.join().unwrap();
+        stdout_thread.join().unwrap();
+        stderr_thread.join().unwrap();
         cmd.wait()?;
 
-        Ok(output)
+        Ok(exceeded.map(|exceeded| (exceeded, started.elapsed())))
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
+    fn forward_lines(
+        stream: impl Read + Send + 'static,
+        sender: Sender<OutputLine>,
+        wrap: fn(String) -> OutputLine,
+    ) -> thread::JoinHandle<()> {
+        thread::spawn(move || {
+            // solutions may print anything, lines that are not valid UTF-8 are forwarded lossily.
+            for line in BufReader::new(stream).split(b'\n') {
+                let Ok(mut line) = line else {
+                    break;
                 };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                if line.last() == Some(&b'\r') {
+                    line.pop();
                 }
-
-                timings.total_nanos += nanos;
-            });
-
-        timings
+                if sender
+                    .send(wrap(String::from_utf8_lossy(&line).into_owned()))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        })
     }
+}
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+/* -------------------------------------------------------------------------- */
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, path::Path};
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
-    }
+    use super::child_commands::{build_bins_in, parse_build_messages};
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
+    #[test]
+    fn parses_build_messages() {
+        let output = [
+            r#"{"reason":"compiler-artifact","target":{"kind":["lib"],"name":"advent_of_code"},"executable":null}"#,
+            r#"{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"01"},"executable":"/aoc/target/release/01"}"#,
+            r#"{"reason":"compiler-message","target":{"kind":["bin"],"name":"01"},"message":{"level":"warning","rendered":"warning: unused variable"}}"#,
+            r#"{"reason":"compiler-message","target":{"kind":["bin"],"name":"06"},"message":{"level":"error","rendered":"error[E0308]: mismatched types\n"}}"#,
+            r#"{"reason":"compiler-message","target":{"kind":["bin"],"name":"06"},"message":{"level":"failure-note","rendered":"For more information"}}"#,
+            r#"{"reason":"build-finished","success":false}"#,
+        ]
+        .join("\n");
 
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+        let builds = parse_build_messages(&output);
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+        assert_eq!(
+            builds.executable("01"),
+            Some(Path::new("/aoc/target/release/01"))
+        );
+        assert_eq!(builds.executable("06"), None);
+        assert_eq!(builds.executable("advent_of_code"), None);
+        assert_eq!(format!("{builds:?}").matches("mismatched types").count(), 1);
+        assert!(!format!("{builds:?}").contains("unused variable"));
+    }
+
+    #[test]
+    fn reports_days_that_do_not_compile() {
+        let dir = env::temp_dir().join(format!("aoc-broken-day-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src").join("bin")).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            "[package]\nname = \"broken_day\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n",
+        )
+        .unwrap();
+        fs::write(dir.join("src").join("bin").join("01.rs"), "fn main() {}\n").unwrap();
+        fs::write(
+            dir.join("src").join("bin").join("02.rs"),
+            "fn main() {\n    let answer: u32 = \"42\";\n}\n",
+        )
+        .unwrap();
+
+        let builds = build_bins_in(&dir, &["01".into(), "02".into()], false).unwrap();
+
+        assert!(builds.executable("01").is_some_and(Path::exists));
+        assert_eq!(builds.executable("02"), None);
+        assert!(!builds.is_failed("01"));
+        assert!(builds.is_failed("02"));
+        assert!(format!("{builds:?}").contains("mismatched types"));
+
+        fs::remove_dir_all(&dir).unwrap();
     }
 }
@@ -2,34 +2,344 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::submissions::{self, Verdict};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    aoc_cli, find_disagreements, parse_param_override, read_examples, record_answer,
+    records_to_json, try_read_file, Answers, BenchStats, OutputFormat, PartRecord, PuzzleId,
+    PuzzleParams, Solution, ANSI_ITALIC, ANSI_RESET,
+};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: impl Into<PuzzleId>,
+    part: u8,
+) -> PartRecord {
+    let puzzle = puzzle.into();
+    let is_timed = env::args().any(|x| x == "--time");
+    let format = OutputFormat::from_args();
+    let record = run_reported(func, input, puzzle, part, is_timed, format);
+    print_record(&record, format);
+
+    if let Some(answer) = &record.answer {
+        submit_result(answer, puzzle, part);
+    }
+
+    record
+}
+
+/// Run the shared parse step of a solution, timed separately from its parts.
+/// Returns `None` if parsing panicked, the parts can't run then.
+pub fn run_parse<T>(
+    parse: impl Fn(&str) -> T,
+    input: &str,
+    puzzle: impl Into<PuzzleId>,
+) -> (Option<T>, PartRecord) {
+    let is_timed = env::args().any(|x| x == "--time");
+    let format = OutputFormat::from_args();
+    let (parsed, record) = run_parse_reported(parse, input, puzzle.into(), is_timed, format);
+    print_record(&record, format);
+    (parsed, record)
+}
+
+/// Run a named variant of a part. Answers of variants are never submitted.
+pub fn run_variant_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: impl Into<PuzzleId>,
+    part: u8,
+    variant: &str,
+) -> PartRecord {
+    let is_timed = env::args().any(|x| x == "--time");
+    let format = OutputFormat::from_args();
+    let record = run_reported(func, input, puzzle.into(), part, is_timed, format)
+        .with_variant(Some(variant));
+    print_record(&record, format);
+    record
+}
+
+/// Reads the input and the parameters of a run from the arguments of the current process.
+/// The input is the puzzle input, or the example named by `--example <name>` with the parameters of that example.
+/// Parameters passed as `--param name=value` are applied on top.
+/// Exits if the example does not exist, an override is malformed or the solution does not declare the parameter.
+pub fn input_from_args<P: PuzzleParams>(puzzle: PuzzleId) -> (String, P) {
+    let args: Vec<String> = env::args().collect();
+
+    let (input, mut overrides) = match args
+        .iter()
+        .position(|x| x == "--example")
+        .and_then(|i| args.get(i + 1))
+    {
+        Some(name) => {
+            let example = read_examples(puzzle)
+                .unwrap_or_else(|e| {
+                    eprintln!("Could not read examples: {e}");
+                    process::exit(1);
+                })
+                .into_iter()
+                .find(|example| &example.name == name)
+                .unwrap_or_else(|| {
+                    eprintln!("Unknown example `{name}`.");
+                    process::exit(1);
+                });
+            (example.input, example.params)
+        }
+        None => {
+            let input = try_read_file("inputs", puzzle).unwrap_or_else(|e| {
+                eprintln!("Could not open input file: {e}");
+                process::exit(1);
+            });
+            (input, vec![])
+        }
+    };
+
+    let params = args
+        .iter()
+        .enumerate()
+        .filter(|(_, x)| *x == "--param")
+        .filter_map(|(i, _)| args.get(i + 1))
+        .map(|x| parse_param_override(x))
+        .collect::<Result<Vec<_>, _>>()
+        .and_then(|params| {
+            overrides.extend(params);
+            P::from_overrides(&overrides)
+        });
+
+    let params = params.unwrap_or_else(|e| {
+        eprintln!("Invalid parameter: {e}.");
+        process::exit(1);
+    });
+
+    (input, params)
+}
+
+/// Selects which implementations of the parts a run executes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Variants {
+    /// Only the default implementation of each part.
+    #[default]
+    Default,
+    /// Only the parts that have a variant with this name.
+    Only(String),
+    /// The default implementations, followed by every variant.
+    All,
+}
+
+impl Variants {
+    /// Reads `--variant <name>` or `--all-variants` from the arguments of the current process.
+    /// Exits if `solution` does not declare the requested variant.
+    pub fn from_args(solution: &dyn Solution) -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        if args.iter().any(|x| x == "--all-variants") {
+            return Self::All;
+        }
+
+        let Some(name) = args
+            .iter()
+            .position(|x| x == "--variant")
+            .and_then(|i| args.get(i + 1))
+        else {
+            return Self::Default;
+        };
+
+        if !solution
+            .variants()
+            .iter()
+            .any(|(variant, _)| variant == name)
+        {
+            eprintln!("Unknown variant `{name}`.");
+            process::exit(1);
+        }
+
+        Self::Only(name.clone())
+    }
+
+    /// The arguments to pass to a solution binary for this selection.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Default => vec![],
+            Self::Only(name) => vec!["--variant".into(), name.clone()],
+            Self::All => vec!["--all-variants".into()],
+        }
+    }
+
+    pub fn runs_default(&self) -> bool {
+        !matches!(self, Self::Only(_))
+    }
+
+    pub fn runs(&self, variant: &str) -> bool {
+        match self {
+            Self::Default => false,
+            Self::Only(name) => name == variant,
+            Self::All => true,
+        }
+    }
+}
+
+/// Warn about variants that answered differently than the default implementation of their part.
+pub fn print_disagreements(records: &[PartRecord]) {
+    for (default, variant) in find_disagreements(records) {
+        eprintln!(
+            "✘ Day {} {}: answered {}, but the default implementation answered {}.",
+            variant.puzzle(),
+            variant.part_label(),
+            variant.answer.as_deref().unwrap_or_default(),
+            default.answer.as_deref().unwrap_or_default(),
+        );
+    }
+}
+
+/// Print the records of a finished run if the output format expects them at the end, i.e. for `--format json`.
+pub fn print_records(records: &[PartRecord]) {
+    if OutputFormat::from_args() == OutputFormat::Json {
+        println!("{}", records_to_json(records));
+    }
+}
+
+/// Exit with a non-zero status if a part of the run failed.
+pub fn exit_on_failure(records: &[PartRecord]) {
+    if records.iter().any(|record| record.status.is_failure()) {
+        process::exit(1);
+    }
+}
+
+/// Run a solution part, printing intermediate results in text mode.
+/// The finished record is left to the caller to print with [`print_record`].
+pub(crate) fn run_reported<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+    is_timed: bool,
+    format: OutputFormat,
+) -> PartRecord {
     let part_str = format!("Part {part}");
+    let timer = Instant::now();
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    // a panicking part is recorded as failed, so the remaining parts still run.
+    let run = catch_panic(|| {
+        run_timed(
+            func,
+            input,
+            |result| {
+                if format.is_text() {
+                    print_result(result, &part_str, "");
+                    if is_timed {
+                        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+                        let _ = stdout().flush();
+                    }
+                }
+            },
+            is_timed,
+        )
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    match run {
+        Ok((result, duration, samples, stats)) => PartRecord::new(
+            puzzle,
+            part,
+            result.map(|x| x.to_string()),
+            duration,
+            samples,
+        )
+        .with_stats(stats),
+        Err(reason) => PartRecord::failed(puzzle, part, reason, timer.elapsed()),
+    }
+}
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+/// Run the shared parse step of a solution, the parse step's counterpart to [`run_reported`].
+pub(crate) fn run_parse_reported<T>(
+    parse: impl Fn(&str) -> T,
+    input: &str,
+    puzzle: PuzzleId,
+    is_timed: bool,
+    format: OutputFormat,
+) -> (Option<T>, PartRecord) {
+    let timer = Instant::now();
+
+    let run = catch_panic(|| {
+        run_timed(
+            parse,
+            input,
+            |_| {
+                if format.is_text() && is_timed {
+                    print!("Parse > {ANSI_ITALIC}benching{ANSI_RESET}");
+                    let _ = stdout().flush();
+                }
+            },
+            is_timed,
+        )
+    });
+
+    match run {
+        Ok((parsed, duration, samples, stats)) => (
+            Some(parsed),
+            PartRecord::parse_step(puzzle, duration, samples).with_stats(stats),
+        ),
+        Err(reason) => (None, PartRecord::failed(puzzle, 0, reason, timer.elapsed())),
+    }
+}
+
+/// Runs `func`, returning the message of the panic if it panics.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(func)).map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".into())
+    })
+}
+
+/// Print a finished part in the given `format`.
+/// Records are streamed for [`OutputFormat::Ndjson`] and left to the caller for [`OutputFormat::Json`].
+pub(crate) fn print_record(record: &PartRecord, format: OutputFormat) {
+    match format {
+        OutputFormat::Text if record.status.is_failure() => {
+            print!("\r");
+            println!(
+                "{}: ✘ {}",
+                record.part_label(),
+                record.failure().unwrap_or_default()
+            );
+        }
+        OutputFormat::Text if record.is_parse_step() => {
+            print!("\r");
+            println!(
+                "{}:{}",
+                record.part_label(),
+                format_duration(&record.duration(), record.samples, record.stats.as_ref())
+            );
+        }
+        OutputFormat::Text => print_result(
+            &record.answer,
+            &record.part_label(),
+            &format!(
+                "{}{}",
+                format_duration(&record.duration(), record.samples, record.stats.as_ref()),
+                format_verdict(record)
+            ),
+        ),
+        OutputFormat::Ndjson => println!("{}", record.to_json_line()),
+        OutputFormat::Json => {}
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Run a solution part. The behavior differs depending on whether the run is timed:
+///  1. if not, the function is executed once.
+///  2. if it is, the function is benched and statistics over the collected samples are returned.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    is_timed: bool,
+) -> (T, Duration, u128, Option<BenchStats>) {
     let timer = Instant::now();
     let result = {
         let input = input.clone();
@@ -43,24 +353,25 @@ fn run_timed<I: Clone, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if is_timed {
+        let (stats, samples) = bench(func, input, &base_time);
+        (result, stats.median(), samples, Some(stats))
     } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
+        (result, base_time, 1, None)
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
-
+/// Bench a solution part. After a few warmup rounds, samples are collected for approx. 1 second of execution time
+/// or 10 samples, whatever take longer, and summarized with outliers discarded.
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (BenchStats, u128) {
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    // warm up caches and the allocator before measuring.
+    for _ in 0..cmp::max(bench_iterations / 10, 1) {
+        black_box(func(black_box(input.clone())));
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
@@ -72,25 +383,32 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     }
 
     (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
+        BenchStats::from_samples(&timers).expect("at least one sample is collected"),
         bench_iterations,
     )
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_duration(duration: &Duration, samples: u128, stats: Option<&BenchStats>) -> String {
+    match stats {
+        Some(stats) => format!(" ({duration:.1?} @ {samples} samples; {stats})"),
+        None if samples == 1 => format!(" ({duration:.1?})"),
+        None => format!(" ({duration:.1?} @ {samples} samples)"),
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+/// Marks an answer as correct or wrong if an accepted answer is recorded for its part.
+fn format_verdict(record: &PartRecord) -> String {
+    let Ok(answers) = Answers::read(record.puzzle()) else {
+        return String::new();
+    };
+
+    match answers.verify(record.part, record.answer.as_deref()) {
+        Some(true) => " ✔".into(),
+        Some(false) => format!(
+            " ✘ expected {}",
+            answers.get(record.part).unwrap_or_default()
+        ),
+        None => String::new(),
     }
 }
 
@@ -131,16 +449,15 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///  2. a session cookie is configured.
+///  3. the submission log does not show that the answer can't be correct.
+///
+/// The submission and the verdict of the server are logged, accepted answers are recorded.
+fn submit_result(result: &str, puzzle: PuzzleId, part: u8) {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return;
     }
 
     if args.len() < 3 {
@@ -156,14 +473,50 @@ fn submit_result<T: Display>(
     };
 
     if part_submit != part {
-        return None;
+        return;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let backend = match aoc_cli::backend() {
+        Ok(backend) => backend,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = backend.check() {
+        eprintln!("{e}");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    if let Some(accepted) = Answers::read(puzzle)
+        .ok()
+        .and_then(|x| x.get(part).map(String::from))
+    {
+        eprintln!("Not submitting: part {part} was already answered correctly with `{accepted}`.");
+        return;
+    }
+
+    println!("Submitting result...");
+
+    let log = submissions::read_from_file();
+    let submission = match submissions::submit_guarded(backend.as_ref(), &log, puzzle, part, result)
+    {
+        Ok(submission) => submission,
+        Err(e) => {
+            eprintln!("Not submitting: {e}.");
+            return;
+        }
+    };
+
+    if let Err(e) = submissions::append(&submission) {
+        eprintln!("Could not log the submission: {e}");
+    }
+
+    if submission.verdict == Verdict::Correct {
+        match record_answer(puzzle, part, result) {
+            Ok(()) => println!("Recorded the accepted answer."),
+            Err(e) => eprintln!("Could not record the accepted answer: {e}"),
+        }
+    }
 }
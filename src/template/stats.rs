@@ -0,0 +1,216 @@
+/// Summary statistics for benchmark samples.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// Statistics over the samples of a benchmark, after outliers were discarded.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchStats {
+    pub min_nanos: f64,
+    pub median_nanos: f64,
+    pub mean_nanos: f64,
+    pub p95_nanos: f64,
+    pub stddev_nanos: f64,
+    /// Number of samples that were discarded as outliers.
+    pub outliers: u128,
+}
+
+impl BenchStats {
+    /// Computes statistics for a set of samples.
+    /// Samples outside of the [Tukey fences](https://en.wikipedia.org/wiki/Outlier#Tukey's_fences)
+    /// (1.5 times the interquartile range below the first or above the third quartile) are discarded first.
+    /// Returns `None` if there are no samples.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted: Vec<f64> = samples.iter().map(|x| x.as_nanos() as f64).collect();
+        sorted.sort_unstable_by(f64::total_cmp);
+
+        let q1 = percentile(&sorted, 25.0);
+        let q3 = percentile(&sorted, 75.0);
+        let iqr = q3 - q1;
+        let (lower, upper) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        let retained: Vec<f64> = sorted
+            .iter()
+            .copied()
+            .filter(|x| *x >= lower && *x <= upper)
+            .collect();
+
+        let count = retained.len() as f64;
+        let mean = retained.iter().sum::<f64>() / count;
+        let variance = if retained.len() > 1 {
+            retained.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (count - 1.0)
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            min_nanos: retained[0],
+            median_nanos: percentile(&retained, 50.0),
+            mean_nanos: mean,
+            p95_nanos: percentile(&retained, 95.0),
+            stddev_nanos: variance.sqrt(),
+            outliers: (sorted.len() - retained.len()) as u128,
+        })
+    }
+
+    /// The representative duration of the benchmark, i.e. its median.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_nanos as u64)
+    }
+}
+
+/// Linearly interpolated percentile of a sorted, non-empty slice.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    let weight = rank - rank.floor();
+    sorted[lower] * (1.0 - weight) + sorted[upper] * weight
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+impl std::fmt::Display for BenchStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {}, mean {}, p95 {}, σ {}",
+            format_nanos(self.min_nanos),
+            format_nanos(self.mean_nanos),
+            format_nanos(self.p95_nanos),
+            format_nanos(self.stddev_nanos),
+        )?;
+
+        if self.outliers > 0 {
+            write!(f, ", {} outliers", self.outliers)?;
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("min_nanos".into(), JsonValue::Number(value.min_nanos));
+        map.insert("median_nanos".into(), JsonValue::Number(value.median_nanos));
+        map.insert("mean_nanos".into(), JsonValue::Number(value.mean_nanos));
+        map.insert("p95_nanos".into(), JsonValue::Number(value.p95_nanos));
+        map.insert("stddev_nanos".into(), JsonValue::Number(value.stddev_nanos));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        Ok(BenchStats {
+            min_nanos: number("min_nanos")?,
+            median_nanos: number("median_nanos")?,
+            mean_nanos: number("mean_nanos")?,
+            p95_nanos: number("p95_nanos")?,
+            stddev_nanos: number("stddev_nanos")?,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+/// Reads optional stats stored under `key`, treating a missing key like `null`.
+pub(crate) fn stats_from_json(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<BenchStats>, String> {
+    match json.get(key) {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => BenchStats::try_from(v).map(Some),
+    }
+}
+
+/// Serializes optional stats to JSON, using `null` if not present.
+pub(crate) fn stats_to_json(stats: Option<&BenchStats>) -> JsonValue {
+    stats.map_or(JsonValue::Null, JsonValue::from)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::BenchStats;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert!(BenchStats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = BenchStats::from_samples(&nanos(&[42])).unwrap();
+        assert_eq!(stats.min_nanos, 42.0);
+        assert_eq!(stats.median_nanos, 42.0);
+        assert_eq!(stats.p95_nanos, 42.0);
+        assert_eq!(stats.stddev_nanos, 0.0);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let stats = BenchStats::from_samples(&nanos(&[4, 1, 3, 2, 5])).unwrap();
+        assert_eq!(stats.min_nanos, 1.0);
+        assert_eq!(stats.median_nanos, 3.0);
+        assert_eq!(stats.mean_nanos, 3.0);
+        assert_eq!(stats.p95_nanos, 4.8);
+        assert!((stats.stddev_nanos - 2.5_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn discards_outliers() {
+        let stats = BenchStats::from_samples(&nanos(&[10, 11, 10, 12, 11, 10, 500])).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.median_nanos, 10.5);
+        assert!(stats.p95_nanos < 500.0);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = BenchStats::from_samples(&nanos(&[10, 11, 10, 12, 11, 10, 500])).unwrap();
+        let json = tinyjson::JsonValue::from(&stats);
+        assert_eq!(BenchStats::try_from(&json).unwrap(), stats);
+    }
+}
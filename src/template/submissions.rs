@@ -0,0 +1,444 @@
+/// Append-only log of submitted answers and the verdicts of the server.
+/// It guards against submissions that can't be correct, as wrong answers are penalized with a wait time.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{Error, Write},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    aoc_cli::{self, PuzzleBackend},
+    year::{year_from_json, year_to_json},
+    Day, PuzzleId, Year,
+};
+
+static SUBMISSIONS_FILE_PATH: &str = "./data/submissions.jsonl";
+
+/// The verdict of the server on a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// Wrong, without a hint in which direction.
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Not checked because the previous answer was submitted too recently.
+    RateLimited {
+        /// The time left to wait, if reported.
+        wait_secs: Option<u64>,
+    },
+    /// The response could not be interpreted, e.g. because the part was already solved.
+    Unknown,
+}
+
+impl Verdict {
+    /// Interprets the response of the server to a submission.
+    pub fn from_output(output: &str) -> Self {
+        if output.contains("That's the right answer") {
+            Self::Correct
+        } else if output.contains("your answer is too high") {
+            Self::TooHigh
+        } else if output.contains("your answer is too low") {
+            Self::TooLow
+        } else if output.contains("That's not the right answer") {
+            Self::Wrong
+        } else if output.contains("You gave an answer too recently") {
+            Self::RateLimited {
+                wait_secs: parse_wait_secs(output),
+            }
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Whether the answer is known to be wrong.
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Self::Wrong | Self::TooHigh | Self::TooLow)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Correct => "correct",
+            Self::Wrong => "wrong",
+            Self::TooHigh => "too high",
+            Self::TooLow => "too low",
+            Self::RateLimited { .. } => "rate-limited",
+            Self::Unknown => "unknown",
+        })
+    }
+}
+
+/// Parses the time left to wait from a message like `You have 1m 30s left to wait.`
+fn parse_wait_secs(output: &str) -> Option<u64> {
+    let start = output.find("You have ")? + "You have ".len();
+    let end = start + output[start..].find(" left to wait")?;
+
+    output[start..end]
+        .split_whitespace()
+        .map(|token| {
+            let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A single submission in the log.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// The year of the puzzle, `None` for the default year.
+    pub year: Option<Year>,
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+impl Submission {
+    pub fn puzzle(&self) -> PuzzleId {
+        PuzzleId::new(self.year, self.day)
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Checks `answer` against the previous submissions of the same part. Refuses, with a reason, if:
+///  1. the part was already answered correctly.
+///  2. the answer was already submitted and is wrong.
+///  3. the answer is a number outside the bounds learned from answers that were too high or too low.
+///  4. the server asked to wait before submitting again, and the wait time is not over yet.
+pub fn check_submission(
+    log: &[Submission],
+    puzzle: PuzzleId,
+    part: u8,
+    answer: &str,
+    now: u64,
+) -> Result<(), String> {
+    let previous: Vec<&Submission> = log
+        .iter()
+        .filter(|x| x.puzzle() == puzzle && x.part == part)
+        .collect();
+
+    if let Some(correct) = previous.iter().find(|x| x.verdict == Verdict::Correct) {
+        return Err(format!(
+            "part {part} was already answered correctly with `{}`",
+            correct.answer
+        ));
+    }
+
+    if let Some(wrong) = previous
+        .iter()
+        .find(|x| x.answer == answer && x.verdict.is_wrong())
+    {
+        return Err(format!(
+            "`{answer}` was already submitted and is {}",
+            wrong.verdict
+        ));
+    }
+
+    if let Ok(value) = answer.parse::<i128>() {
+        let bound = |verdict: Verdict| {
+            previous
+                .iter()
+                .filter(move |x| x.verdict == verdict)
+                .filter_map(|x| x.answer.parse::<i128>().ok())
+        };
+
+        if let Some(high) = bound(Verdict::TooHigh).min().filter(|high| value >= *high) {
+            return Err(format!(
+                "`{answer}` is not lower than `{high}`, which is too high"
+            ));
+        }
+
+        if let Some(low) = bound(Verdict::TooLow).max().filter(|low| value <= *low) {
+            return Err(format!(
+                "`{answer}` is not higher than `{low}`, which is too low"
+            ));
+        }
+    }
+
+    let wait_until = log
+        .iter()
+        .filter(|x| x.puzzle() == puzzle)
+        .filter_map(|x| match x.verdict {
+            Verdict::RateLimited {
+                wait_secs: Some(wait_secs),
+            } => Some(x.timestamp + wait_secs),
+            _ => None,
+        })
+        .max();
+
+    if let Some(wait_until) = wait_until.filter(|x| *x > now) {
+        return Err(format!(
+            "the server asked to wait another {}s before submitting again",
+            wait_until - now
+        ));
+    }
+
+    Ok(())
+}
+
+/// Submits `answer` to `backend` unless [`check_submission`] refuses it, and returns the submission to log.
+pub fn submit_guarded(
+    backend: &dyn PuzzleBackend,
+    log: &[Submission],
+    puzzle: PuzzleId,
+    part: u8,
+    answer: &str,
+) -> Result<Submission, String> {
+    let timestamp = now();
+    check_submission(log, puzzle, part, answer, timestamp)?;
+
+    let message = aoc_cli::submit(backend, puzzle, part, answer).map_err(|e| e.to_string())?;
+
+    Ok(Submission {
+        timestamp,
+        year: puzzle.year,
+        day: puzzle.day,
+        part,
+        answer: answer.into(),
+        verdict: Verdict::from_output(&message),
+    })
+}
+
+/// Appends a submission to the log as a single line of JSON.
+pub fn append(submission: &Submission) -> Result<(), Error> {
+    let json = JsonValue::from(submission)
+        .stringify()
+        .map_err(Error::other)?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(SUBMISSIONS_FILE_PATH)?;
+    writeln!(file, "{json}")
+}
+
+/// Reads all submissions of the log. Lines that can't be parsed are skipped.
+pub fn read_from_file() -> Vec<Submission> {
+    fs::read_to_string(SUBMISSIONS_FILE_PATH)
+        .map(|s| parse_lines(&s))
+        .unwrap_or_default()
+}
+
+fn parse_lines(s: &str) -> Vec<Submission> {
+    s.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| Submission::try_from(line).ok())
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("year".into(), year_to_json(value.year));
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+        if let Verdict::RateLimited {
+            wait_secs: Some(wait_secs),
+        } = value.verdict
+        {
+            map.insert("wait_secs".into(), JsonValue::Number(wait_secs as f64));
+        }
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+
+        let map = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let number = |key: &str| map.get(key).and_then(|v| v.get::<f64>()).copied();
+        let string = |key: &str| map.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        let verdict = match string("verdict").as_deref() {
+            Some("correct") => Verdict::Correct,
+            Some("wrong") => Verdict::Wrong,
+            Some("too high") => Verdict::TooHigh,
+            Some("too low") => Verdict::TooLow,
+            Some("rate-limited") => Verdict::RateLimited {
+                wait_secs: number("wait_secs").map(|x| x as u64),
+            },
+            Some("unknown") => Verdict::Unknown,
+            _ => return Err("Expected submission.verdict to be a verdict.".into()),
+        };
+
+        Ok(Submission {
+            timestamp: number("timestamp")
+                .map(|x| x as u64)
+                .ok_or("Expected submission.timestamp to be a number.")?,
+            year: year_from_json(map, "submission")?,
+            day: string("day")
+                .and_then(|day| Day::from_str(&day).ok())
+                .ok_or("Expected submission.day to be a Day struct.")?,
+            part: number("part")
+                .map(|x| x as u8)
+                .ok_or("Expected submission.part to be a number.")?,
+            answer: string("answer").ok_or("Expected submission.answer to be a string.")?,
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{check_submission, parse_lines, Submission, Verdict};
+    use crate::{day, template::PuzzleId};
+
+    fn get_mock_submission(answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            timestamp: 1_733_029_200,
+            year: None,
+            day: day!(1),
+            part: 1,
+            answer: answer.into(),
+            verdict,
+        }
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::from_output("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_output("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::from_output("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::from_output("That's not the right answer. If you're stuck, ..."),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            Verdict::from_output("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 30s left to wait."),
+            Verdict::RateLimited { wait_secs: Some(90) }
+        );
+        assert_eq!(
+            Verdict::from_output("You don't seem to be solving the right level."),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let submissions = [
+            get_mock_submission("42", Verdict::TooLow),
+            get_mock_submission(
+                "43",
+                Verdict::RateLimited {
+                    wait_secs: Some(34),
+                },
+            ),
+        ];
+        let lines: Vec<String> = submissions
+            .iter()
+            .map(|x| JsonValue::from(x).stringify().unwrap())
+            .collect();
+        assert_eq!(
+            parse_lines(&format!("{}\n{{ broken\n\n{}\n", lines[0], lines[1])),
+            submissions
+        );
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let puzzle = PuzzleId::from(day!(1));
+        let log = [
+            get_mock_submission("abc", Verdict::Wrong),
+            get_mock_submission("100", Verdict::TooHigh),
+            get_mock_submission("50", Verdict::TooHigh),
+            get_mock_submission("10", Verdict::TooLow),
+        ];
+        let now = 1_733_029_300;
+        assert!(check_submission(&log, puzzle, 1, "abc", now).is_err());
+        assert!(check_submission(&log, puzzle, 1, "abd", now).is_ok());
+        assert!(check_submission(&log, puzzle, 1, "50", now).is_err());
+        assert!(check_submission(&log, puzzle, 1, "70", now).is_err());
+        assert!(check_submission(&log, puzzle, 1, "10", now).is_err());
+        assert!(check_submission(&log, puzzle, 1, "49", now).is_ok());
+        assert!(check_submission(&log, puzzle, 2, "abc", now).is_ok());
+        assert!(check_submission(&log, day!(2).into(), 1, "abc", now).is_ok());
+    }
+
+    #[test]
+    fn refuses_solved_parts_and_rate_limits() {
+        let puzzle = PuzzleId::from(day!(1));
+        let log = [get_mock_submission("42", Verdict::Correct)];
+        assert!(check_submission(&log, puzzle, 1, "43", 1_733_029_300).is_err());
+
+        let log = [get_mock_submission(
+            "42",
+            Verdict::RateLimited {
+                wait_secs: Some(60),
+            },
+        )];
+        assert!(check_submission(&log, puzzle, 1, "42", 1_733_029_230).is_err());
+        assert!(check_submission(&log, puzzle, 2, "42", 1_733_029_230).is_err());
+        assert!(check_submission(&log, puzzle, 1, "42", 1_733_029_260).is_ok());
+    }
+
+    #[test]
+    fn submits_answers() {
+        use super::submit_guarded;
+        use crate::template::aoc_client::{tests::mock_server, AocClient};
+
+        let (base_url, _) = mock_server(vec![(
+            200,
+            "<article><p>That's not the right answer; your answer is too high.</p></article>",
+        )]);
+        let client = AocClient::new(&base_url, "abc");
+
+        let puzzle = PuzzleId::new(Some(crate::year!(2024)), day!(1));
+        let submission = submit_guarded(&client, &[], puzzle, 1, "100").unwrap();
+        assert_eq!(submission.verdict, Verdict::TooHigh);
+        assert_eq!(submission.answer, "100");
+        assert!(submit_guarded(&client, &[submission], puzzle, 1, "101").is_err());
+    }
+}
@@ -0,0 +1,310 @@
+/// Templates for scaffolding solutions, built-in presets and templates of the user.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{encryption, read_examples, PuzzleId};
+
+macro_rules! builtin_template {
+    ($name:literal) => {
+        (
+            $name,
+            include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/templates/",
+                $name,
+                ".txt"
+            )),
+        )
+    };
+}
+
+/// Templates shipped with the repository, in `src/templates`.
+const BUILTIN_TEMPLATES: [(&str, &str); 5] = [
+    builtin_template!("default"),
+    builtin_template!("graph"),
+    builtin_template!("grid"),
+    builtin_template!("parser"),
+    builtin_template!("vm"),
+];
+
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// The directory holding templates of the user, `AOC_TEMPLATE_DIR` or `~/.config/advent-of-code/templates`.
+fn get_user_template_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("AOC_TEMPLATE_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config_dir.join("advent-of-code").join("templates"))
+}
+
+/// Loads the template `name`. Templates of the user take precedence over built-in templates of the same name.
+pub fn load_template(name: &str) -> Result<String, String> {
+    load_template_from(get_user_template_dir().as_deref(), name)
+}
+
+fn load_template_from(user_dir: Option<&Path>, name: &str) -> Result<String, String> {
+    if let Some(dir) = user_dir {
+        if let Ok(template) = fs::read_to_string(dir.join(format!("{name}.txt"))) {
+            return Ok(template);
+        }
+    }
+
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, template)| template.to_string())
+        .ok_or_else(|| {
+            format!(
+                "unknown template `{name}`. Available templates: {}",
+                template_names(user_dir).join(", ")
+            )
+        })
+}
+
+/// Names of the built-in templates and the templates of the user, sorted and without duplicates.
+fn template_names(user_dir: Option<&Path>) -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .collect();
+
+    if let Some(entries) = user_dir.and_then(|dir| fs::read_dir(dir).ok()) {
+        names.extend(entries.filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "txt" {
+                return None;
+            }
+            Some(path.file_stem()?.to_str()?.to_string())
+        }));
+    }
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Values of the placeholders of a template.
+#[derive(Debug)]
+pub struct TemplateContext {
+    pub puzzle: PuzzleId,
+    /// Title of the puzzle, e.g. `Day 1: Historian Hysteria`.
+    pub title: Option<String>,
+    /// Answers of the first example, as they appear in the examples manifest.
+    pub example_part_1: Option<String>,
+    pub example_part_2: Option<String>,
+}
+
+impl TemplateContext {
+    /// Collects the title from the downloaded puzzle description and the example answers from the examples manifest, if present.
+    pub fn read(puzzle: PuzzleId) -> Self {
+        let title = encryption::read_to_string(format!("data/puzzles/{puzzle}.md"))
+            .ok()
+            .and_then(|description| parse_title(&description));
+
+        let example = read_examples(puzzle)
+            .ok()
+            .and_then(|examples| examples.into_iter().next());
+
+        Self {
+            puzzle,
+            title,
+            example_part_1: example.as_ref().and_then(|x| x.part_1.clone()),
+            example_part_2: example.and_then(|x| x.part_2),
+        }
+    }
+}
+
+/// Replaces the placeholders of `template`:
+/// - `%DAY_NUMBER%`: the day without padding, e.g. `1`.
+/// - `%DAY%`: the padded day, e.g. `01`.
+/// - `%YEAR%`: the year of the puzzle, falling back to the default year.
+/// - `%YEAR_ARGUMENT%`: `, year = 2023` for puzzles of other years than the default year.
+/// - `%TITLE%`: the title of the puzzle, e.g. `Day 1: Historian Hysteria`, or `Day 1` if the puzzle was not downloaded.
+/// - `%EXAMPLE_PART_ONE%` and `%EXAMPLE_PART_TWO%`: the expected answer of the first example as an
+///   expression, e.g. `Some(11)`, or `None` if unknown.
+/// - `%ANSWER_TYPE_PART_ONE%` and `%ANSWER_TYPE_PART_TWO%`: a type that holds the expected answer of the first example,
+///   e.g. `u64` for `35000000000` or `String` for `6,1`, and `u32` if unknown.
+pub fn render_template(template: &str, context: &TemplateContext) -> String {
+    let puzzle = context.puzzle;
+    let day_number = puzzle.day.into_inner().to_string();
+
+    let year = puzzle
+        .year_or_default()
+        .map(|year| year.into_inner().to_string())
+        .unwrap_or_default();
+
+    let year_argument = puzzle
+        .year
+        .map(|year| format!(", year = {year}"))
+        .unwrap_or_default();
+
+    let title = context
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("Day {day_number}"));
+
+    template
+        .replace("%DAY_NUMBER%", &day_number)
+        .replace("%DAY%", &puzzle.day.to_string())
+        .replace("%YEAR_ARGUMENT%", &year_argument)
+        .replace("%YEAR%", &year)
+        .replace("%TITLE%", &title)
+        .replace(
+            "%EXAMPLE_PART_ONE%",
+            &answer_expression(context.example_part_1.as_deref()),
+        )
+        .replace(
+            "%EXAMPLE_PART_TWO%",
+            &answer_expression(context.example_part_2.as_deref()),
+        )
+        .replace(
+            "%ANSWER_TYPE_PART_ONE%",
+            answer_type(context.example_part_1.as_deref()),
+        )
+        .replace(
+            "%ANSWER_TYPE_PART_TWO%",
+            answer_type(context.example_part_2.as_deref()),
+        )
+}
+
+/// The title in the first heading of a puzzle description, e.g. `## --- Day 1: Historian Hysteria ---`.
+fn parse_title(description: &str) -> Option<String> {
+    let heading = description.lines().find(|line| line.starts_with('#'))?;
+    let title = heading
+        .trim_start_matches('#')
+        .trim()
+        .trim_matches('-')
+        .trim();
+
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// The smallest of the usual answer types that holds `answer`, see [`answer_expression`].
+fn answer_type(answer: Option<&str>) -> &'static str {
+    let Some(answer) = answer else {
+        return "u32";
+    };
+
+    if answer.parse::<u32>().is_ok() {
+        "u32"
+    } else if answer.parse::<u64>().is_ok() {
+        "u64"
+    } else if answer.parse::<i64>().is_ok() {
+        "i64"
+    } else if answer.parse::<i128>().is_ok() {
+        "i128"
+    } else {
+        "String"
+    }
+}
+
+/// The expected `answer` as an expression of the type returned by [`answer_type`].
+fn answer_expression(answer: Option<&str>) -> String {
+    match answer {
+        None => "None".into(),
+        Some(answer) if answer.parse::<i128>().is_ok() => format!("Some({answer})"),
+        Some(answer) => format!("Some({answer:?}.to_string())"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs};
+
+    use super::{
+        answer_type, load_template_from, parse_title, render_template, TemplateContext,
+        BUILTIN_TEMPLATES,
+    };
+    use crate::{day, template::PuzzleId, year};
+
+    #[test]
+    fn renders_placeholders() {
+        let context = TemplateContext {
+            puzzle: PuzzleId::new(Some(year!(2023)), day!(5)),
+            title: Some("Day 5: If You Give A Seed A Fertilizer".into()),
+            example_part_1: Some("35".into()),
+            example_part_2: Some("6,1".into()),
+        };
+
+        assert_eq!(
+            render_template(
+                "// %TITLE% (%YEAR%)\nsolution!(%DAY_NUMBER%%YEAR_ARGUMENT%); // %DAY%\n%EXAMPLE_PART_ONE% %EXAMPLE_PART_TWO%",
+                &context
+            ),
+            "// Day 5: If You Give A Seed A Fertilizer (2023)\nsolution!(5, year = 2023); // 05\nSome(35) Some(\"6,1\".to_string())"
+        );
+
+        let context = TemplateContext {
+            puzzle: PuzzleId::new(None, day!(12)),
+            title: None,
+            example_part_1: None,
+            example_part_2: None,
+        };
+        assert_eq!(
+            render_template("%TITLE%%YEAR_ARGUMENT% %EXAMPLE_PART_ONE%", &context),
+            "Day 12 None"
+        );
+    }
+
+    #[test]
+    fn renders_answer_types() {
+        assert_eq!(answer_type(None), "u32");
+        assert_eq!(answer_type(Some("11")), "u32");
+        assert_eq!(answer_type(Some("65601038650482")), "u64");
+        assert_eq!(answer_type(Some("-3")), "i64");
+        assert_eq!(answer_type(Some("6,1")), "String");
+
+        let context = TemplateContext {
+            puzzle: PuzzleId::new(None, day!(18)),
+            title: None,
+            example_part_1: Some("22".into()),
+            example_part_2: Some("6,1".into()),
+        };
+        assert_eq!(
+            render_template(
+                "Option<%ANSWER_TYPE_PART_ONE%> = %EXAMPLE_PART_ONE%; Option<%ANSWER_TYPE_PART_TWO%> = %EXAMPLE_PART_TWO%;",
+                &context
+            ),
+            "Option<u32> = Some(22); Option<String> = Some(\"6,1\".to_string());"
+        );
+    }
+
+    #[test]
+    fn parses_titles() {
+        assert_eq!(
+            parse_title("## --- Day 1: Historian Hysteria ---\n\nThe *Chief Historian*"),
+            Some("Day 1: Historian Hysteria".into())
+        );
+        assert_eq!(parse_title("no heading"), None);
+    }
+
+    #[test]
+    fn loads_user_templates() {
+        let dir = env::temp_dir().join(format!("aoc-templates-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("mine.txt"), "mine").unwrap();
+        fs::write(dir.join("grid.txt"), "my grid").unwrap();
+
+        assert_eq!(load_template_from(Some(&dir), "mine").unwrap(), "mine");
+        assert_eq!(load_template_from(Some(&dir), "grid").unwrap(), "my grid");
+        assert_eq!(
+            load_template_from(Some(&dir), "default").unwrap(),
+            BUILTIN_TEMPLATES[0].1
+        );
+        assert_eq!(
+            load_template_from(Some(&dir), "nope").unwrap_err(),
+            "unknown template `nope`. Available templates: default, graph, grid, mine, parser, vm"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
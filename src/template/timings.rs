@@ -1,17 +1,165 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    stats::{stats_from_json, stats_to_json},
+    year::{year_from_json, year_to_json},
+    BenchStats, Day, PartRecord, PuzzleId, Status, Year,
+};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
+    /// The year of the puzzle, `None` for the default year.
+    pub year: Option<Year>,
     pub day: Day,
+    /// Name of the extra binary the timings were taken from, `None` for the puzzle's own binary.
+    pub bin: Option<String>,
+    /// Name of the variant the timings were taken from, `None` for the default implementations.
+    pub variant: Option<String>,
+    /// Duration of the shared parse step, only present for solutions that declare one.
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    pub parse_stats: Option<BenchStats>,
+    pub part_1_stats: Option<BenchStats>,
+    pub part_2_stats: Option<BenchStats>,
+    /// Parts that failed in this run, e.g. because they timed out, part `0` being the parse step.
+    /// They have no duration and are not stored, see [`Timings::merge`].
+    pub failed_parts: Vec<u8>,
+}
+
+impl Timing {
+    /// Collect the timings of the solved parts of a puzzle, run by its own binary or the extra binary `bin`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_records(puzzle: PuzzleId, bin: Option<&str>, records: &[PartRecord]) -> Self {
+        let mut timing = Timing {
+            year: puzzle.year,
+            day: puzzle.day,
+            bin: bin.map(Into::into),
+            variant: None,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            failed_parts: vec![],
+        };
+
+        timing.failed_parts = records
+            .iter()
+            .filter(|r| r.status.is_failure())
+            .map(|r| r.part)
+            .collect();
+
+        for record in records.iter().filter(|r| r.status == Status::Solved) {
+            let duration_str = Some(format!("{:.1?}", record.duration()));
+
+            match record.part {
+                0 => {
+                    timing.parse = duration_str;
+                    timing.parse_stats = record.stats;
+                }
+                1 => {
+                    timing.part_1 = duration_str;
+                    timing.part_1_stats = record.stats;
+                }
+                2 => {
+                    timing.part_2 = duration_str;
+                    timing.part_2_stats = record.stats;
+                }
+                _ => {}
+            }
+
+            timing.total_nanos += record.duration_nanos as f64;
+        }
+
+        timing
+    }
+
+    pub fn puzzle(&self) -> PuzzleId {
+        PuzzleId::new(self.year, self.day)
+    }
+
+    /// Collect the timings of a run, one for the default implementations and one for each variant.
+    pub fn from_run(puzzle: PuzzleId, bin: Option<&str>, records: &[PartRecord]) -> Vec<Self> {
+        let mut variants: Vec<Option<&str>> = vec![None];
+
+        for record in records {
+            let variant = record.variant.as_deref();
+            if !variants.contains(&variant) {
+                variants.push(variant);
+            }
+        }
+
+        variants
+            .into_iter()
+            .map(|variant| {
+                let records: Vec<PartRecord> = records
+                    .iter()
+                    .filter(|record| record.variant.as_deref() == variant)
+                    .cloned()
+                    .collect();
+
+                let mut timing = Self::from_records(puzzle, bin, &records);
+                timing.variant = variant.map(Into::into);
+                timing
+            })
+            .collect()
+    }
+
+    /// Name of the binary the timings were taken from, e.g. `01` or `26`.
+    pub fn bin_name(&self) -> String {
+        self.bin
+            .clone()
+            .unwrap_or_else(|| self.puzzle().to_string())
+    }
+
+    /// Describes where the timings were taken from if not the day's own, default implementation,
+    /// e.g. `26` for an extra binary or `fast` for a variant.
+    pub fn source(&self) -> Option<String> {
+        match (&self.bin, &self.variant) {
+            (Some(bin), Some(variant)) => Some(format!("{bin}, {variant}")),
+            (Some(source), None) | (None, Some(source)) => Some(source.clone()),
+            (None, None) => None,
+        }
+    }
+
+    fn key(&self) -> (PuzzleId, Option<&str>, Option<&str>) {
+        (self.puzzle(), self.bin.as_deref(), self.variant.as_deref())
+    }
+
+    /// The duration of a part in nanoseconds, part `0` being the parse step. Uses the benchmark median if available
+    /// and falls back to the formatted duration for timings stored without statistics.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        let (duration_str, stats) = match part {
+            0 => (&self.parse, &self.parse_stats),
+            1 => (&self.part_1, &self.part_1_stats),
+            2 => (&self.part_2, &self.part_2_stats),
+            _ => return None,
+        };
+
+        match stats {
+            Some(stats) => Some(stats.median_nanos),
+            None => duration_str.as_deref().and_then(parse_duration),
+        }
+    }
+}
+
+/// Parses a duration formatted with `{:?}` to nanoseconds, e.g. `74.1µs`.
+fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let parse = |postfix: &str| s.strip_suffix(postfix)?.parse::<f64>().ok();
+
+    parse("ns")
+        .or_else(|| parse("µs").map(|x| x * 1000_f64))
+        .or_else(|| parse("ms").map(|x| x * 1_000_000_f64))
+        .or_else(|| parse("s").map(|x| x * 1_000_000_000_f64))
 }
 
 /// Represents benchmark times for a set of days.
@@ -38,32 +186,49 @@ impl Timings {
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Timings with failed parts don't overwrite stored ones, a timed out part has no duration to store.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            if timing.failed_parts.is_empty() || self.get_matching(timing).is_none() {
+                data.push(timing.clone());
+            }
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+            if !data.iter().any(|t| t.key() == timing.key()) {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by(|a, b| a.key().cmp(&b.key()));
         Timings { data }
     }
 
-    /// Sum up total duration of timings as millis.
-    pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+    /// Returns the timings of the default implementations in the puzzle's own binary.
+    pub fn get(&self, puzzle: PuzzleId) -> Option<&Timing> {
+        self.data.iter().find(|t| t.key() == (puzzle, None, None))
+    }
+
+    /// Returns the timings taken from the same binary and variant as `timing`.
+    pub fn get_matching(&self, timing: &Timing) -> Option<&Timing> {
+        self.data.iter().find(|t| t.key() == timing.key())
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
+    /// Sum up total duration of timings as millis. Extra binaries and variants are not included.
+    pub fn total_millis(&self) -> f64 {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .filter(|x| x.source().is_none())
+            .map(|x| x.total_nanos)
+            .sum::<f64>()
+            / 1_000_000_f64
+    }
+
+    pub fn is_day_complete(&self, puzzle: PuzzleId) -> bool {
+        self.get(puzzle)
+            .is_some_and(|t| t.part_nanos(1).is_some() && t.part_nanos(2).is_some())
     }
 }
 
@@ -87,7 +252,14 @@ impl TryFrom<String> for Timings {
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        Timings::try_from(&json)
+    }
+}
+
+impl TryFrom<&JsonValue> for Timings {
+    type Error = String;
 
+    fn try_from(json: &JsonValue) -> Result<Self, Self::Error> {
         let json_data = json
             .get::<HashMap<String, JsonValue>>()
             .ok_or("expected JSON document to be an object.")?
@@ -111,9 +283,29 @@ impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert("year".into(), year_to_json(value.year));
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "bin".into(),
+            value.bin.clone().map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "variant".into(),
+            value
+                .variant
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        map.insert(
+            "parse".into(),
+            value
+                .parse
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
@@ -133,6 +325,19 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "parse_stats".into(),
+            stats_to_json(value.parse_stats.as_ref()),
+        );
+        map.insert(
+            "part_1_stats".into(),
+            stats_to_json(value.part_1_stats.as_ref()),
+        );
+        map.insert(
+            "part_2_stats".into(),
+            stats_to_json(value.part_2_stats.as_ref()),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -145,12 +350,22 @@ impl TryFrom<&JsonValue> for Timing {
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
 
+        // timings stored before multi-year support have no year, i.e. belong to the default year.
+        let year = year_from_json(json, "timing")?;
+
         let day = json
             .get("day")
             .and_then(|v| v.get::<String>())
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        let bin = json.get("bin").and_then(|v| v.get::<String>()).cloned();
+
+        let variant = json.get("variant").and_then(|v| v.get::<String>()).cloned();
+
+        // the parse step is optional, timings of solutions without one don't have it.
+        let parse = json.get("parse").and_then(|v| v.get::<String>()).cloned();
+
         let part_1 = json
             .get("part_1")
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
@@ -166,11 +381,24 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // stats were added later, timings stored before that don't have them.
+        let parse_stats = stats_from_json(json, "parse_stats")?;
+        let part_1_stats = stats_from_json(json, "part_1_stats")?;
+        let part_2_stats = stats_from_json(json, "part_2_stats")?;
+
         Ok(Timing {
+            year,
             day,
+            bin,
+            variant,
+            parse,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            parse_stats,
+            part_1_stats,
+            part_2_stats,
+            failed_parts: vec![],
         })
     }
 }
@@ -187,22 +415,46 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
+                    year: None,
                     day: day!(1),
+                    bin: None,
+                    variant: None,
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    failed_parts: vec![],
                 },
                 Timing {
+                    year: None,
                     day: day!(2),
+                    bin: None,
+                    variant: None,
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    failed_parts: vec![],
                 },
                 Timing {
+                    year: None,
                     day: day!(4),
+                    bin: None,
+                    variant: None,
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    failed_parts: vec![],
                 },
             ],
         }
@@ -278,42 +530,202 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
+                    year: None,
                     day: day!(1),
+                    bin: None,
+                    variant: None,
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    failed_parts: vec![],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1).into()), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
+                    year: None,
                     day: day!(1),
+                    bin: None,
+                    variant: None,
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    failed_parts: vec![],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1).into()), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
                 data: vec![Timing {
+                    year: None,
                     day: day!(1),
+                    bin: None,
+                    variant: None,
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    failed_parts: vec![],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1).into()), false);
+        }
+    }
+
+    mod from_records {
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::{
+                limits::LimitExceeded,
+                timings::{Timing, Timings},
+                PartRecord,
+            },
+        };
+
+        #[test]
+        fn collects_solved_parts() {
+            let timing = Timing::from_records(
+                day!(1).into(),
+                None,
+                &[
+                    PartRecord::new(
+                        day!(1),
+                        1,
+                        Some("0".into()),
+                        Duration::from_nanos(74),
+                        100_000,
+                    ),
+                    PartRecord::new(
+                        day!(1),
+                        2,
+                        Some("10".into()),
+                        Duration::from_micros(74_130),
+                        99_999,
+                    ),
+                ],
+            );
+            assert_eq!(timing.total_nanos, 74_130_074_f64);
+            assert_eq!(timing.part_1.unwrap(), "74.0ns");
+            assert_eq!(timing.part_2.unwrap(), "74.1ms");
+        }
+
+        #[test]
+        fn skips_unsolved_parts() {
+            let timing = Timing::from_records(
+                day!(1).into(),
+                None,
+                &[
+                    PartRecord::new(day!(1), 1, None, Duration::from_millis(2), 1),
+                    PartRecord::new(day!(1), 2, None, Duration::from_millis(1), 1),
+                ],
+            );
+            assert_eq!(timing.total_nanos, 0_f64);
+            assert_eq!(timing.part_1.is_none(), true);
+            assert_eq!(timing.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn collects_parse_steps() {
+            let timing = Timing::from_records(
+                day!(1).into(),
+                None,
+                &[
+                    PartRecord::parse_step(day!(1), Duration::from_millis(2), 1),
+                    PartRecord::new(day!(1), 1, Some("0".into()), Duration::from_millis(1), 1),
+                ],
+            );
+            assert_eq!(timing.total_nanos, 3_000_000_f64);
+            assert_eq!(timing.parse.unwrap(), "2.0ms");
+            assert_eq!(timing.part_1.unwrap(), "1.0ms");
+        }
+
+        #[test]
+        fn lists_killed_parts() {
+            let timing = Timing::from_records(
+                day!(14).into(),
+                None,
+                &[
+                    PartRecord::new(day!(14), 1, Some("7".into()), Duration::from_millis(1), 1),
+                    PartRecord::killed(
+                        day!(14),
+                        2,
+                        LimitExceeded::Timeout(Duration::from_secs(10)),
+                        Duration::from_secs(10),
+                    ),
+                ],
+            );
+            assert_eq!(timing.total_nanos, 1_000_000_f64);
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.failed_parts, vec![2]);
+
+            let timings = Timings { data: vec![timing] };
+            assert!(!timings.is_day_complete(day!(14).into()));
+        }
+    }
+
+    mod part_nanos {
+        use crate::{day, template::timings::Timing, template::BenchStats};
+
+        fn get_mock_timing() -> Timing {
+            Timing {
+                year: None,
+                day: day!(1),
+                bin: None,
+                variant: None,
+                parse: None,
+                part_1: Some("74.1µs".into()),
+                part_2: Some("2.5s".into()),
+                total_nanos: 0_f64,
+                parse_stats: None,
+                part_1_stats: None,
+                part_2_stats: None,
+                failed_parts: vec![],
+            }
+        }
+
+        #[test]
+        fn parses_formatted_durations() {
+            let timing = get_mock_timing();
+            assert_eq!(timing.part_nanos(1), Some(74_100_f64));
+            assert_eq!(timing.part_nanos(2), Some(2_500_000_000_f64));
+            assert_eq!(timing.part_nanos(3), None);
+        }
+
+        #[test]
+        fn prefers_stats() {
+            let mut timing = get_mock_timing();
+            timing.part_1_stats = Some(BenchStats {
+                min_nanos: 1.0,
+                median_nanos: 2.0,
+                mean_nanos: 3.0,
+                p95_nanos: 4.0,
+                stddev_nanos: 5.0,
+                outliers: 0,
+            });
+            assert_eq!(timing.part_nanos(1), Some(2_f64));
         }
     }
 
@@ -330,10 +742,18 @@ mod tests {
             let timings = get_mock_timings();
             let other = Timings {
                 data: vec![Timing {
+                    year: None,
                     day: day!(3),
+                    bin: None,
+                    variant: None,
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    failed_parts: vec![],
                 }],
             };
             let merged = timings.merge(&other);
@@ -350,10 +770,18 @@ mod tests {
 
             let other = Timings {
                 data: vec![Timing {
+                    year: None,
                     day: day!(2),
+                    bin: None,
+                    variant: None,
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    failed_parts: vec![],
                 }],
             };
             let merged = timings.merge(&other);
@@ -380,5 +808,37 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn keeps_stored_timings_of_failed_runs() {
+            let timings = get_mock_timings();
+            let mut failed = timings.data[1].clone();
+            failed.part_2 = None;
+            failed.failed_parts = vec![2];
+            let mut new_day = failed.clone();
+            new_day.day = day!(3);
+
+            let merged = timings.merge(&Timings {
+                data: vec![failed, new_day],
+            });
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(merged.data[1].part_2, Some("40ms".into()));
+            assert_eq!(merged.data[2].day, day!(3));
+        }
+
+        #[test]
+        fn keeps_extra_binaries_apart() {
+            let timings = get_mock_timings();
+            let mut extra = timings.data[0].clone();
+            extra.bin = Some("26".into());
+
+            let merged = timings.merge(&Timings { data: vec![extra] });
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(merged.data[0].bin, None);
+            assert_eq!(merged.data[1].bin, Some("26".into()));
+            assert_eq!(merged.get(day!(1).into()).unwrap().bin, None);
+            assert!(merged.get_matching(&merged.data[1]).is_some());
+            assert_eq!(merged.total_millis(), timings.total_millis());
+        }
     }
 }
@@ -0,0 +1,84 @@
+/// Polls files for changes, which needs no platform-specific file system events.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// Watches a set of files by comparing their modification times. Files that don't exist yet are watched as
+/// well, creating or removing them counts as a change.
+pub struct FileWatcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl FileWatcher {
+    pub fn new(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        Self {
+            files: paths
+                .into_iter()
+                .map(|path| {
+                    let modified = get_modified(&path);
+                    (path, modified)
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the files that changed since the last call, or since the watcher was created.
+    pub fn changed(&mut self) -> Vec<&Path> {
+        let mut changed = vec![];
+
+        for (path, last_modified) in &mut self.files {
+            let modified = get_modified(path);
+            if modified != *last_modified {
+                *last_modified = modified;
+                changed.push(path.as_path());
+            }
+        }
+
+        changed
+    }
+}
+
+fn get_modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|x| x.modified()).ok()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        env,
+        fs::{self, File},
+        time::{Duration, SystemTime},
+    };
+
+    use super::FileWatcher;
+
+    #[test]
+    fn detects_changes() {
+        let dir = env::temp_dir().join(format!("aoc-watcher-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (existing, created) = (dir.join("01.rs"), dir.join("01.txt"));
+        fs::write(&existing, "a").unwrap();
+
+        let mut watcher = FileWatcher::new([existing.clone(), created.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        // set the modification time explicitly, file systems may store it with a coarse resolution.
+        File::options()
+            .write(true)
+            .open(&existing)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+        assert_eq!(watcher.changed(), [existing.as_path()]);
+        assert!(watcher.changed().is_empty());
+
+        fs::write(&created, "b").unwrap();
+        assert_eq!(watcher.changed(), [created.as_path()]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
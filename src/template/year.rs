@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use tinyjson::JsonValue;
+
+/// The year the first advent of code took place.
+const FIRST_YEAR: u16 = 2015;
+
+/// A valid year of advent of code (i.e. 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if advent of code took place in it,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The default year of this repository, configured via `AOC_YEAR` in `.cargo/config.toml`.
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Reads the optional `year` of a record or timing, treating a missing key like `null`, i.e. the default year.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn year_from_json(
+    json: &HashMap<String, JsonValue>,
+    name: &str,
+) -> Result<Option<Year>, String> {
+    match json.get("year") {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => v
+            .get::<f64>()
+            .and_then(|year| Year::new(*year as u16))
+            .map(Some)
+            .ok_or(format!("Expected {name}.year to be null or a year.")),
+    }
+}
+
+/// Serializes an optional year to JSON, using `null` for the default year.
+pub(crate) fn year_to_json(year: Option<Year>) -> JsonValue {
+    year.map_or(JsonValue::Null, |year| JsonValue::Number(year.0.into()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015,
+            concat!("invalid year `", $year, "`, expecting 2015 or later"),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2023".parse::<Year>().unwrap(), Year(2023));
+        assert_eq!("2015".parse::<Year>().unwrap(), Year(2015));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("5".parse::<Year>().is_err());
+        assert!("twenty".parse::<Year>().is_err());
+    }
+}